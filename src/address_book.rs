@@ -0,0 +1,104 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use alloy_core::primitives::Address;
+
+use crate::{
+    api::FireblocksClient,
+    types::{DestinationTransferPeerPath, FireblocksError, PeerType, WalletContainerResponse},
+};
+
+/// Maps EVM addresses to the Fireblocks peer that owns them, so transfers to
+/// known addresses use the matching destination type instead of a one time
+/// address.
+#[derive(Debug, Clone, Default)]
+pub struct AddressBook {
+    /// Peer type and id keyed by address
+    entries: HashMap<Address, (PeerType, String)>,
+    /// When the address book was loaded from Fireblocks
+    loaded_at: Option<Instant>,
+}
+
+impl AddressBook {
+    /// Load vault addresses and the internal, external and contract wallets
+    /// holding `asset_id`.
+    ///
+    /// When an address belongs to several peers, vaults win over internal
+    /// wallets, which win over external wallets, which win over contracts.
+    pub async fn load(
+        client: &FireblocksClient,
+        asset_id: &str,
+        vault_accounts: &HashMap<u64, Address>,
+    ) -> Result<Self, FireblocksError> {
+        let mut address_book = Self {
+            loaded_at: Some(Instant::now()),
+            ..Self::default()
+        };
+        for (vault_id, address) in vault_accounts {
+            address_book.insert(*address, PeerType::VAULT_ACCOUNT, vault_id.to_string());
+        }
+
+        let internal_wallets = client.get_internal_wallets().await?;
+        address_book.insert_wallets(&internal_wallets, PeerType::INTERNAL_WALLET, asset_id);
+        let external_wallets = client.get_external_wallets().await?;
+        address_book.insert_wallets(&external_wallets, PeerType::EXTERNAL_WALLET, asset_id);
+        let contract_wallets = client.get_contract_wallets().await?;
+        address_book.insert_wallets(&contract_wallets, PeerType::CONTRACT, asset_id);
+
+        Ok(address_book)
+    }
+
+    /// Add an address, keeping any peer already registered for it
+    pub fn insert(&mut self, address: Address, peer_type: PeerType, id: String) {
+        self.entries.entry(address).or_insert((peer_type, id));
+    }
+
+    /// Add the addresses of every wallet holding the asset
+    fn insert_wallets(
+        &mut self,
+        wallets: &[WalletContainerResponse],
+        peer_type: PeerType,
+        asset_id: &str,
+    ) {
+        for wallet in wallets {
+            let addresses = wallet
+                .assets
+                .iter()
+                .filter(|asset| asset.id == asset_id)
+                .filter_map(|asset| asset.address.as_ref()?.parse::<Address>().ok());
+            for address in addresses {
+                self.insert(address, peer_type.clone(), wallet.id.clone());
+            }
+        }
+    }
+
+    /// Get the destination for an address, if it belongs to a known peer
+    pub fn resolve(&self, address: &Address) -> Option<DestinationTransferPeerPath> {
+        self.entries
+            .get(address)
+            .map(|(peer_type, id)| DestinationTransferPeerPath {
+                peer_type: peer_type.clone(),
+                id: Some(id.clone()),
+                one_time_address: None,
+            })
+    }
+
+    /// Number of known addresses
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no addresses are known
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether the address book was loaded from Fireblocks less than
+    /// `interval` ago
+    pub fn loaded_within(&self, interval: Duration) -> bool {
+        self.loaded_at
+            .is_some_and(|loaded_at| loaded_at.elapsed() < interval)
+    }
+}
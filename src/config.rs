@@ -0,0 +1,469 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::types::{
+    ApiBaseUrl, ChainId, FeeLevel, FireblocksError, FireblocksProviderConfig, RateLimits,
+};
+
+/// Prefix of the environment variables read by [`FireblocksProviderConfig::from_env`]
+pub const ENV_PREFIX: &str = "FIREBLOCKS_";
+
+/// Config keys as written in config files, with the suffix of their
+/// environment variable
+const KEYS: &[(&str, &str)] = &[
+    ("api_key", "API_KEY"),
+    ("private_key", "API_PRIVATE_KEY"),
+    ("private_key_path", "API_PRIVATE_KEY_PATH"),
+    ("api_base_url", "API_BASE_URL"),
+    ("chain_id", "CHAIN_ID"),
+    ("rpc_url", "RPC_URL"),
+    ("vault_account_ids", "VAULT_ACCOUNT_IDS"),
+    ("fallback_fee_level", "FALLBACK_FEE_LEVEL"),
+    ("note", "NOTE"),
+    ("polling_interval", "POLLING_INTERVAL"),
+    ("polling_timeout", "POLLING_TIMEOUT"),
+    ("one_time_addresses_enabled", "ONE_TIME_ADDRESSES_ENABLED"),
+    ("external_tx_id", "EXTERNAL_TX_ID"),
+    ("user_agent", "USER_AGENT"),
+    ("asset_id", "ASSET_ID"),
+    (
+        "log_transaction_status_changes",
+        "LOG_TRANSACTION_STATUS_CHANGES",
+    ),
+    ("log_requests_and_responses", "LOG_REQUESTS_AND_RESPONSES"),
+    ("enhanced_error_handling", "ENHANCED_ERROR_HANDLING"),
+    ("gasless_gas_tank_vault_id", "GASLESS_GAS_TANK_VAULT_ID"),
+    ("proxy_path", "PROXY_PATH"),
+    ("connect_timeout", "CONNECT_TIMEOUT"),
+    ("request_timeout", "REQUEST_TIMEOUT"),
+    ("max_request_attempts", "MAX_REQUEST_ATTEMPTS"),
+    ("rate_limits", "RATE_LIMITS"),
+    (
+        "address_book_refresh_interval",
+        "ADDRESS_BOOK_REFRESH_INTERVAL",
+    ),
+];
+
+/// Where config values are read from
+enum Source {
+    /// Environment variables starting with the prefix
+    Env(String),
+    /// A config file, optionally overlaid with one of its profiles
+    File(PathBuf, Option<String>),
+}
+
+impl Source {
+    /// Name of a key as the user sets it, used to point at missing values
+    fn key_name(&self, key: &str) -> String {
+        match self {
+            Source::Env(prefix) => format!("{}{}", prefix, env_suffix(key)),
+            Source::File(path, Some(profile)) => {
+                format!("{}: profiles.{}.{}", path.display(), profile, key)
+            }
+            Source::File(path, None) => format!("{}: {}", path.display(), key),
+        }
+    }
+
+    /// Directory relative private key paths are resolved against
+    fn base_dir(&self) -> Option<&Path> {
+        match self {
+            Source::Env(_) => None,
+            Source::File(path, _) => path.parent(),
+        }
+    }
+}
+
+/// Raw config values, with the name of the key each was read from
+struct ConfigValues {
+    source: Source,
+    values: HashMap<&'static str, (String, Value)>,
+}
+
+impl ConfigValues {
+    /// Read the environment variables with the given prefix
+    fn from_env(prefix: &str) -> Result<Self, FireblocksError> {
+        let mut values = HashMap::new();
+        for (key, suffix) in KEYS {
+            let name = format!("{}{}", prefix, suffix);
+            match std::env::var(&name) {
+                Ok(value) if !value.trim().is_empty() => {
+                    values.insert(*key, (name, Value::String(value)));
+                }
+                Ok(_) | Err(std::env::VarError::NotPresent) => {}
+                Err(e) => return Err(FireblocksError::ConfigError(name, e.to_string())),
+            }
+        }
+        Ok(Self {
+            source: Source::Env(prefix.to_string()),
+            values,
+        })
+    }
+
+    /// Read the top level keys of a config file, overlaid with the keys of
+    /// the profile if one is given
+    fn from_document(
+        path: &Path,
+        document: Value,
+        profile: Option<&str>,
+    ) -> Result<Self, FireblocksError> {
+        let file = path.display().to_string();
+        let Value::Object(mut document) = document else {
+            return Err(FireblocksError::ConfigError(
+                file,
+                "expected a table of config keys".to_string(),
+            ));
+        };
+        let profiles = document.remove("profiles");
+
+        let mut values = HashMap::new();
+        insert_keys(&mut values, document, &format!("{}: ", file))?;
+        if let Some(profile) = profile {
+            let prefix = format!("{}: profiles.{}", file, profile);
+            match profiles.as_ref().and_then(|profiles| profiles.get(profile)) {
+                Some(Value::Object(keys)) => {
+                    // A private key set in the profile replaces the top level
+                    // one, whether it is set inline or as a path
+                    if keys.contains_key("private_key") || keys.contains_key("private_key_path") {
+                        values.remove("private_key");
+                        values.remove("private_key_path");
+                    }
+                    insert_keys(&mut values, keys.clone(), &format!("{}.", prefix))?
+                }
+                Some(_) => {
+                    return Err(FireblocksError::ConfigError(
+                        prefix,
+                        "expected a table of config keys".to_string(),
+                    ))
+                }
+                None => {
+                    return Err(FireblocksError::ConfigError(
+                        prefix,
+                        "profile not found".to_string(),
+                    ))
+                }
+            }
+        }
+
+        Ok(Self {
+            source: Source::File(path.to_path_buf(), profile.map(str::to_string)),
+            values,
+        })
+    }
+
+    /// Parse the value of a key if it is set
+    fn optional<T>(
+        &mut self,
+        key: &str,
+        parse: impl FnOnce(&Value) -> Result<T, String>,
+    ) -> Result<Option<T>, FireblocksError> {
+        match self.values.remove(key) {
+            Some((name, value)) => parse(&value)
+                .map(Some)
+                .map_err(|e| FireblocksError::ConfigError(name, e)),
+            None => Ok(None),
+        }
+    }
+
+    /// Parse the value of a key that must be set
+    fn required<T>(
+        &mut self,
+        key: &str,
+        parse: impl FnOnce(&Value) -> Result<T, String>,
+    ) -> Result<T, FireblocksError> {
+        self.optional(key, parse)?.ok_or_else(|| {
+            FireblocksError::ConfigError(self.source.key_name(key), "missing value".to_string())
+        })
+    }
+
+    /// Read the private key, set either inline or as the path of a PEM file.
+    /// It may be left out when requests are signed by an external signer
+    fn private_key(&mut self) -> Result<Option<String>, FireblocksError> {
+        let private_key = self.optional("private_key", parse_string)?;
+        let private_key_path = self.values.remove("private_key_path");
+        match (private_key, private_key_path) {
+            (Some(private_key), None) => Ok(Some(private_key)),
+            (None, Some((name, value))) => {
+                let path = PathBuf::from(
+                    parse_string(&value)
+                        .map_err(|e| FireblocksError::ConfigError(name.clone(), e))?,
+                );
+                let path = match self.source.base_dir() {
+                    Some(base_dir) if path.is_relative() => base_dir.join(path),
+                    _ => path,
+                };
+                std::fs::read_to_string(&path).map(Some).map_err(|e| {
+                    FireblocksError::ConfigError(
+                        name,
+                        format!("failed to read {}: {}", path.display(), e),
+                    )
+                })
+            }
+            (Some(_), Some((name, _))) => Err(FireblocksError::ConfigError(
+                name,
+                "set either the private key or its path, not both".to_string(),
+            )),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// Validate the values and build a config from them
+    fn into_config(mut self) -> Result<FireblocksProviderConfig, FireblocksError> {
+        let api_key = self.required("api_key", parse_string)?;
+        let private_key = self.private_key()?;
+        let api_base_url = self
+            .optional("api_base_url", parse_api_base_url)?
+            .unwrap_or(ApiBaseUrl::Production);
+        let chain_id = self.required("chain_id", parse_chain_id)?;
+
+        let mut config =
+            FireblocksProviderConfig::without_private_key(api_key, api_base_url, chain_id);
+        config.private_key = private_key.map(Into::into);
+        macro_rules! set {
+            ($($key:ident: $parse:expr),* $(,)?) => {
+                $(
+                    if let Some(value) = self.optional(stringify!($key), $parse)? {
+                        config.$key = Some(value);
+                    }
+                )*
+            };
+        }
+        set!(
+            rpc_url: parse_string,
+            vault_account_ids: parse_ids,
+            fallback_fee_level: parse_fee_level,
+            note: parse_string,
+            polling_interval: parse_u64,
+            one_time_addresses_enabled: parse_bool,
+            external_tx_id: parse_string,
+            user_agent: parse_string,
+            asset_id: parse_string,
+            log_transaction_status_changes: parse_bool,
+            log_requests_and_responses: parse_bool,
+            enhanced_error_handling: parse_bool,
+            gasless_gas_tank_vault_id: parse_u64,
+            proxy_path: parse_string,
+            connect_timeout: parse_u64,
+            request_timeout: parse_u64,
+            max_request_attempts: parse_u32,
+            rate_limits: parse_rate_limits,
+            address_book_refresh_interval: parse_u64,
+        );
+        if let Some(polling_timeout) = self.optional("polling_timeout", parse_polling_timeout)? {
+            config.polling_timeout = polling_timeout;
+        }
+        Ok(config)
+    }
+}
+
+impl FireblocksProviderConfig {
+    /// Read the config from `FIREBLOCKS_*` environment variables, e.g.
+    /// `FIREBLOCKS_API_KEY`, `FIREBLOCKS_API_PRIVATE_KEY_PATH`,
+    /// `FIREBLOCKS_API_BASE_URL`, `FIREBLOCKS_CHAIN_ID` and
+    /// `FIREBLOCKS_VAULT_ACCOUNT_IDS`.
+    ///
+    /// Every config key can be set through the upper case variable of the
+    /// same name, except the private key which is read from
+    /// `FIREBLOCKS_API_PRIVATE_KEY` or `FIREBLOCKS_API_PRIVATE_KEY_PATH`.
+    pub fn from_env() -> Result<Self, FireblocksError> {
+        ConfigValues::from_env(ENV_PREFIX)?.into_config()
+    }
+
+    /// Read the config of a profile from `FIREBLOCKS_<PROFILE>_*`
+    /// environment variables, e.g. `FIREBLOCKS_EU_API_KEY`
+    pub fn from_env_profile(profile: &str) -> Result<Self, FireblocksError> {
+        let prefix = format!("{}{}_", ENV_PREFIX, profile.to_uppercase());
+        ConfigValues::from_env(&prefix)?.into_config()
+    }
+
+    /// Read the config from a TOML file.
+    ///
+    /// Keys are those of the config, with `private_key_path` as an
+    /// alternative to `private_key`, resolved relative to the file. Keys
+    /// under `[profiles.<name>]` override the top level keys when the
+    /// profile is selected.
+    pub fn from_toml_file(
+        path: impl AsRef<Path>,
+        profile: Option<&str>,
+    ) -> Result<Self, FireblocksError> {
+        let path = path.as_ref();
+        let document = toml::from_str(&read_file(path)?)
+            .map_err(|e| FireblocksError::ConfigError(path.display().to_string(), e.to_string()))?;
+        ConfigValues::from_document(path, document, profile)?.into_config()
+    }
+
+    /// Read the config from a JSON file, laid out like the TOML file read
+    /// by [`FireblocksProviderConfig::from_toml_file`]
+    pub fn from_json_file(
+        path: impl AsRef<Path>,
+        profile: Option<&str>,
+    ) -> Result<Self, FireblocksError> {
+        let path = path.as_ref();
+        let document = serde_json::from_str(&read_file(path)?)
+            .map_err(|e| FireblocksError::ConfigError(path.display().to_string(), e.to_string()))?;
+        ConfigValues::from_document(path, document, profile)?.into_config()
+    }
+}
+
+/// Suffix of the environment variable setting a config key
+fn env_suffix(key: &str) -> &str {
+    KEYS.iter()
+        .find(|(name, _)| *name == key)
+        .map(|(_, suffix)| *suffix)
+        .unwrap_or(key)
+}
+
+/// Add the keys of a config table, rejecting unknown keys
+fn insert_keys(
+    values: &mut HashMap<&'static str, (String, Value)>,
+    keys: serde_json::Map<String, Value>,
+    prefix: &str,
+) -> Result<(), FireblocksError> {
+    for (key, value) in keys {
+        let name = format!("{}{}", prefix, key);
+        match KEYS.iter().find(|(known, _)| *known == key) {
+            Some((known, _)) => {
+                values.insert(*known, (name, value));
+            }
+            None => {
+                return Err(FireblocksError::ConfigError(
+                    name,
+                    "unknown key".to_string(),
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+fn read_file(path: &Path) -> Result<String, FireblocksError> {
+    std::fs::read_to_string(path)
+        .map_err(|e| FireblocksError::ConfigError(path.display().to_string(), e.to_string()))
+}
+
+fn parse_string(value: &Value) -> Result<String, String> {
+    match value {
+        Value::String(value) => Ok(value.clone()),
+        _ => Err(format!("expected a string, got {}", value)),
+    }
+}
+
+fn parse_u64(value: &Value) -> Result<u64, String> {
+    match value {
+        Value::Number(number) => number.as_u64(),
+        Value::String(value) => value.trim().parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| format!("expected a non negative integer, got {}", value))
+}
+
+/// Parse a polling timeout, where `"none"` or null waits indefinitely
+fn parse_polling_timeout(value: &Value) -> Result<Option<u64>, String> {
+    match value {
+        Value::Null => Ok(None),
+        Value::String(value) if value.trim().eq_ignore_ascii_case("none") => Ok(None),
+        _ => parse_u64(value)
+            .map(Some)
+            .map_err(|_| format!("expected a non negative integer or \"none\", got {}", value)),
+    }
+}
+
+fn parse_u32(value: &Value) -> Result<u32, String> {
+    parse_u64(value)?
+        .try_into()
+        .map_err(|_| format!("expected an integer up to {}, got {}", u32::MAX, value))
+}
+
+fn parse_bool(value: &Value) -> Result<bool, String> {
+    match value {
+        Value::Bool(value) => Some(*value),
+        Value::String(value) => match value.trim().to_lowercase().as_str() {
+            "true" | "1" => Some(true),
+            "false" | "0" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    }
+    .ok_or_else(|| format!("expected true or false, got {}", value))
+}
+
+/// Parse a list of ids, or a comma separated string of them
+fn parse_ids(value: &Value) -> Result<Vec<u64>, String> {
+    match value {
+        Value::Array(ids) => ids.iter().map(parse_u64).collect(),
+        Value::String(ids) => ids
+            .split(',')
+            .map(|id| parse_u64(&Value::String(id.to_string())))
+            .collect(),
+        _ => Err(format!("expected a list of ids, got {}", value)),
+    }
+}
+
+/// Parse a chain by numeric id or name, e.g. `11155111` or `"sepolia"`
+fn parse_chain_id(value: &Value) -> Result<ChainId, String> {
+    let chain_id = match value {
+        Value::Number(id) => id.as_u64().and_then(ChainId::from_id),
+        Value::String(chain) => match chain.trim().parse::<u64>() {
+            Ok(id) => ChainId::from_id(id),
+            Err(_) => serde_json::from_value(Value::String(chain.trim().to_uppercase())).ok(),
+        },
+        _ => None,
+    };
+    chain_id.ok_or_else(|| format!("unsupported chain {}", value))
+}
+
+/// Parse an API base url by name, e.g. `"sandbox"`, or as a custom URL
+fn parse_api_base_url(value: &Value) -> Result<ApiBaseUrl, String> {
+    match value {
+        Value::String(url) => match url.trim().to_lowercase().as_str() {
+            "production" => Ok(ApiBaseUrl::Production),
+            "sandbox" => Ok(ApiBaseUrl::Sandbox),
+            "eu" => Ok(ApiBaseUrl::EU),
+            "eu2" => Ok(ApiBaseUrl::EU2),
+            lower if lower.starts_with("http://") || lower.starts_with("https://") => {
+                Ok(ApiBaseUrl::Custom(url.trim().to_string()))
+            }
+            _ => Err(format!(
+                "expected production, sandbox, eu, eu2 or a URL, got {}",
+                value
+            )),
+        },
+        value => parse_json(value),
+    }
+}
+
+fn parse_fee_level(value: &Value) -> Result<FeeLevel, String> {
+    match value {
+        Value::String(level) => match level.trim().to_uppercase().as_str() {
+            "HIGH" => Some(FeeLevel::HIGH),
+            "MEDIUM" => Some(FeeLevel::MEDIUM),
+            "LOW" => Some(FeeLevel::LOW),
+            _ => None,
+        },
+        _ => None,
+    }
+    .ok_or_else(|| format!("expected HIGH, MEDIUM or LOW, got {}", value))
+}
+
+/// Parse a value with its serde representation, also accepted as a JSON string
+fn parse_json<T: DeserializeOwned>(value: &Value) -> Result<T, String> {
+    match value {
+        Value::String(json) => serde_json::from_str(json),
+        value => serde_json::from_value(value.clone()),
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// Parse rate limits, rejecting any that would block every request
+fn parse_rate_limits(value: &Value) -> Result<RateLimits, String> {
+    let rate_limits: RateLimits = parse_json(value)?;
+    rate_limits.validate().map_err(|e| match e {
+        FireblocksError::ConfigError(key, message) => format!("{}: {}", key, message),
+        e => e.to_string(),
+    })?;
+    Ok(rate_limits)
+}
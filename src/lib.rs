@@ -1,3 +1,9 @@
+pub mod address_book;
 pub mod api;
+pub mod config;
 pub mod provider;
+pub mod rate_limit;
+pub mod request_signer;
+pub mod signer;
 pub mod types;
+pub mod wallet;
@@ -1,15 +1,49 @@
 use std::{
+    borrow::Cow,
     collections::HashMap,
     sync::{Arc, RwLock},
+    time::{Duration, Instant},
 };
 
-use alloy_core::primitives::Address;
-use alloy_provider::{Provider, ProviderBuilder};
-use alloy_transport::{TransportError, TransportErrorKind};
+use alloy_core::{
+    dyn_abi::TypedData,
+    primitives::{
+        hex,
+        utils::{format_ether, format_units},
+        Address, PrimitiveSignature, TxKind, B256,
+    },
+    sol_types::{Eip712Domain, SolStruct},
+};
+use alloy_json_rpc::{RpcParam, RpcReturn};
+use alloy_provider::{
+    network::Ethereum, PendingTransactionBuilder, Provider, ProviderBuilder, ProviderCall,
+    RootProvider, SendableTx,
+};
+use alloy_rpc_client::NoParams;
+use alloy_rpc_types_eth::TransactionRequest;
+use alloy_transport::{
+    BoxTransport, RpcError, TransportError, TransportErrorKind, TransportResult,
+};
+use async_trait::async_trait;
+use futures::{future, TryStreamExt};
+use reqwest::StatusCode;
+use serde::Serialize;
+use serde_json::{value::RawValue, Value};
 
 use crate::{
-    api::FireblocksClient,
-    types::{Asset, FireblocksError, FireblocksProviderConfig, TransactionStatus},
+    address_book::AddressBook,
+    api::{FireblocksClient, FireblocksClientBuilder},
+    rate_limit::RateLimiter,
+    request_signer::RequestSigner,
+    signer::FireblocksSigner,
+    types::{
+        ApiErrorKind, Asset, DestinationTransferPeerPath, FeeBump, FireblocksError,
+        FireblocksProviderConfig, OneTimeAddress, PeerType, ProviderRpcError, RequestArguments,
+        RequestOptions, RetryPolicy, SpeedUpOutcome, TransactionArguments, TransactionDetails,
+        TransactionOperation, TransactionOutcome, TransactionStatus, TransferPeerPath,
+        TransferPeerPathResponse, VaultAccountsFilter, DEFAULT_ADDRESS_BOOK_REFRESH_INTERVAL,
+    },
+    wallet::FireblocksWallet,
 };
 
 /// A Web3 provider that integrates with Fireblocks custody
@@ -22,17 +56,51 @@ pub struct FireblocksProvider {
     pub config: FireblocksProviderConfig,
     /// Cached account addresses
     pub accounts: Arc<RwLock<HashMap<u64, Address>>>,
+    /// Cached address book, loaded on first use
+    pub address_book: Arc<RwLock<Option<AddressBook>>>,
 }
 
 impl FireblocksProvider {
-    /// Create a new Fireblocks provider
-    /// Create a new Fireblocks provider
+    /// Create a new Fireblocks provider signing requests with the private key
+    /// in the config
     pub async fn new(config: FireblocksProviderConfig) -> Result<Self, TransportError> {
-        // Clone only necessary fields for client initialization
-        let client_private_key = config.private_key.clone();
-        let client_api_key = config.api_key.clone();
-        let client_api_base_url = config.api_base_url.clone();
+        let private_key = config.private_key.clone().ok_or_else(|| {
+            TransportErrorKind::custom(FireblocksError::ConfigError(
+                "private_key".to_string(),
+                "missing value, use FireblocksProvider::from_signer to sign with an external signer"
+                    .to_string(),
+            ))
+        })?;
+        let client_builder = FireblocksClient::builder(
+            private_key,
+            config.api_key.clone(),
+            config.api_base_url.clone(),
+        );
+        Self::from_client_builder(config, client_builder).await
+    }
+
+    /// Create a new Fireblocks provider signing requests with the given
+    /// signer, e.g. one backed by a KMS or HSM. The private key in the config
+    /// is not used and may be left out
+    pub async fn from_signer(
+        config: FireblocksProviderConfig,
+        signer: Arc<dyn RequestSigner>,
+    ) -> Result<Self, TransportError> {
+        let client_builder = FireblocksClient::builder_from_signer(
+            signer,
+            config.api_key.clone(),
+            config.api_base_url.clone(),
+        );
+        Self::from_client_builder(config, client_builder).await
+    }
 
+    /// Create a new Fireblocks provider using a prebuilt client. The client
+    /// settings in the config, such as the proxy, timeouts and rate limits,
+    /// are not applied to it
+    pub async fn from_client(
+        config: FireblocksProviderConfig,
+        fireblocks: FireblocksClient,
+    ) -> Result<Self, TransportError> {
         // Initialize RPC provider using direct config access
         let inner = if let Some(rpc) = &config.rpc_url {
             Arc::new(ProviderBuilder::new().on_builtin(rpc.as_str()).await?)
@@ -45,16 +113,13 @@ impl FireblocksProvider {
             )
         };
 
-        // Initialize Fireblocks SDK with cloned values
-        let fireblocks =
-            FireblocksClient::new(client_private_key, client_api_key, client_api_base_url);
-
         // Create the provider with empty accounts
         let provider = Self {
             inner,
             fireblocks,
             config, // Original intact config
             accounts: Arc::new(RwLock::new(HashMap::new())),
+            address_book: Arc::new(RwLock::new(None)),
         };
 
         provider
@@ -65,17 +130,40 @@ impl FireblocksProvider {
         Ok(provider)
     }
 
+    /// Apply the client settings in the config and build the provider
+    async fn from_client_builder(
+        config: FireblocksProviderConfig,
+        client_builder: FireblocksClientBuilder,
+    ) -> Result<Self, TransportError> {
+        // Share one HTTP client across requests
+        let mut client_builder =
+            client_builder.with_user_agent(format_user_agent(config.user_agent.as_deref()));
+        if let Some(proxy) = &config.proxy_path {
+            client_builder = client_builder.with_proxy(proxy.clone());
+        }
+        if let Some(connect_timeout) = config.connect_timeout {
+            client_builder =
+                client_builder.with_connect_timeout(Duration::from_millis(connect_timeout));
+        }
+        if let Some(request_timeout) = config.request_timeout {
+            client_builder = client_builder.with_timeout(Duration::from_millis(request_timeout));
+        }
+        if let Some(max_attempts) = config.max_request_attempts {
+            client_builder = client_builder
+                .with_retry_policy(RetryPolicy::default().with_max_attempts(max_attempts));
+        }
+        if let Some(rate_limits) = &config.rate_limits {
+            let rate_limiter = RateLimiter::new(rate_limits).map_err(TransportErrorKind::custom)?;
+            client_builder = client_builder.with_rate_limiter(rate_limiter);
+        }
+        let fireblocks = client_builder.build().map_err(TransportErrorKind::custom)?;
+
+        Self::from_client(config, fireblocks).await
+    }
+
     /// Get the user agent for program
     pub fn get_user_agent(&self) -> String {
-        if let Some(custom_ua) = &self.config.user_agent {
-            format!(
-                "alloy-fireblocks/{} {}",
-                env!("CARGO_PKG_VERSION"),
-                custom_ua
-            )
-        } else {
-            format!("alloy-fireblocks/{}", env!("CARGO_PKG_VERSION"))
-        }
+        format_user_agent(self.config.user_agent.as_deref())
     }
 
     /// Get Vault accounts
@@ -87,17 +175,17 @@ impl FireblocksProvider {
             .as_ref()
             .ok_or(FireblocksError::MissingAssetIDError())?;
 
-        // Get paged vault accounts
-        // TODO: deal with pagination
-        let response = self.fireblocks.get_vaults().await?;
-
-        // Filter and parse accounts
-        let account_ids = response
-            .accounts
-            .into_iter()
-            .filter(|account| account.assets.iter().any(|asset| asset.id == *asset_id))
-            .map(|account| account.parse_id())
-            .collect::<Result<Vec<_>, _>>()?;
+        // Walk every page of vault accounts holding the asset
+        let filter = VaultAccountsFilter::default().with_asset_id(asset_id.clone());
+        let account_ids = self
+            .fireblocks
+            .vault_accounts_stream(filter)
+            .try_filter(|account| {
+                future::ready(account.assets.iter().any(|asset| asset.id == *asset_id))
+            })
+            .and_then(|account| future::ready(account.parse_id()))
+            .try_collect::<Vec<_>>()
+            .await?;
 
         Ok(account_ids)
     }
@@ -183,72 +271,915 @@ impl FireblocksProvider {
     //     Ok(())
     // }
 
-    // /// Create and submit a transaction via Fireblocks
-    // async fn create_fireblocks_transaction(
-    //     &self,
-    //     tx: &TransactionRequest,
-    // ) -> Result<B256, TransportError> {
-    //     // Validate the transaction's chain ID
-    //     if let Some(chain_id) = tx.chain_id {
-    //         if chain_id != self.config.chain_id {
-    //             return Err(TransportError::Custom("Chain ID mismatch".to_string()));
-    //         }
-    //     }
+    /// Get the cached vault addresses ordered by vault account id
+    pub fn get_addresses(&self) -> Result<Vec<Address>, FireblocksError> {
+        let accounts = self.accounts.read().map_err(|_| {
+            FireblocksError::SynchronizationError("Failed to acquire read lock".to_string())
+        })?;
 
-    //     // Get the source vault account
-    //     let vault_id = self.get_vault_account_id(tx.from.unwrap_or_default())?;
-
-    //     // Prepare transaction arguments
-    //     let args = TransactionArguments {
-    //         operation: if tx.data.is_some() {
-    //             TransactionOperation::CONTRACT_CALL
-    //         } else {
-    //             TransactionOperation::TRANSFER
-    //         },
-    //         asset_id: self.config.asset_id.clone().unwrap(),
-    //         source: vault_id.to_string(),
-    //         destination: tx.to.map(|addr| addr.to_string()).unwrap_or_default(),
-    //         amount: tx.value.unwrap_or_default().to_string(),
-    //         note: self.config.note.clone(),
-    //         external_tx_id: None,
-    //         extra_parameters: tx.data.as_ref().map(|data| {
-    //             json!({
-    //                 "contractCallData": format!("0x{}", hex::encode(data))
-    //             })
-    //         }),
-    //     };
+        let mut accounts = accounts.iter().collect::<Vec<_>>();
+        accounts.sort_by_key(|(vault_id, _)| **vault_id);
+        Ok(accounts.into_iter().map(|(_, address)| *address).collect())
+    }
 
-    //     // Submit transaction and wait for completion
-    //     let response = self.fireblocks.create_transaction(args).await?;
+    /// Get the vault account id owning a cached address
+    pub fn get_vault_account_id(&self, address: &Address) -> Result<u64, FireblocksError> {
+        let accounts = self.accounts.read().map_err(|_| {
+            FireblocksError::SynchronizationError("Failed to acquire read lock".to_string())
+        })?;
 
-    //     // Poll for transaction completion
-    //     let mut status = response.status;
-    //     while !is_final_status(&status) {
-    //         tokio::time::sleep(Duration::from_secs(1)).await;
-    //         let info = self.fireblocks.get_transaction_by_id(response.id).await?;
-    //         status = info.status;
-    //     }
+        accounts
+            .iter()
+            .find(|(_, account)| *account == address)
+            .map(|(vault_id, _)| *vault_id)
+            .ok_or_else(|| FireblocksError::UnknownAccountError(address.to_string()))
+    }
 
-    //     if !is_successful_status(&status) {
-    //         return Err(TransportError::Custom(format!(
-    //             "Transaction failed with status: {:?}",
-    //             status
-    //         )));
-    //     }
+    /// Create and submit a transaction via Fireblocks, returning the on-chain hash
+    /// once the transaction has reached a final status
+    pub async fn create_fireblocks_transaction(
+        &self,
+        tx: &TransactionRequest,
+    ) -> Result<B256, FireblocksError> {
+        // Validate the transaction's chain ID
+        let expected_chain_id = self.config.chain_id.clone() as u64;
+        if let Some(chain_id) = tx.chain_id {
+            if chain_id != expected_chain_id {
+                return Err(FireblocksError::ChainIdMismatchError(
+                    expected_chain_id,
+                    chain_id,
+                ));
+            }
+        }
 
-    //     // Return transaction hash
-    //     Ok(B256::from_str(&response.tx_hash.unwrap_or_default())?)
-    // }
+        // Get the source vault account, the lowest vault one when unset
+        let from = match tx.from {
+            Some(from) => from,
+            None => *self
+                .get_addresses()?
+                .first()
+                .ok_or(FireblocksError::FailedToPopulateAccountsError())?,
+        };
+        let vault_id = self.get_vault_account_id(&from)?;
+
+        let to = match tx.to {
+            Some(TxKind::Call(to)) => to,
+            _ => return Err(FireblocksError::MissingToAddressError()),
+        };
+
+        let asset_id = self
+            .config
+            .asset_id
+            .clone()
+            .ok_or(FireblocksError::MissingAssetIDError())?;
+
+        // Transactions carrying calldata are contract calls, everything else is a transfer
+        let call_data = tx.input.input().filter(|data| !data.is_empty());
+        let operation = if call_data.is_some() {
+            TransactionOperation::CONTRACT_CALL
+        } else {
+            TransactionOperation::TRANSFER
+        };
+
+        // Prepare transaction arguments
+        let mut builder = TransactionArguments::builder(
+            asset_id,
+            operation,
+            TransferPeerPath {
+                peer_type: PeerType::VAULT_ACCOUNT,
+                id: vault_id.to_string(),
+            },
+        )
+        .with_destination(self.resolve_destination(&to).await?)
+        .with_amount(format_ether(tx.value.unwrap_or_default()));
+        if let Some(data) = call_data {
+            builder = builder.with_contract_call_data(data.to_string());
+        }
+        if let Some(gas_limit) = tx.gas {
+            builder = builder.with_gas_limit(gas_limit.to_string());
+        }
+
+        // Use the request's fees if set, otherwise let Fireblocks estimate them
+        builder = match (
+            tx.max_fee_per_gas,
+            tx.max_priority_fee_per_gas,
+            tx.gas_price,
+        ) {
+            (Some(max_fee), Some(priority_fee), _) => {
+                builder.with_eip1559_fees(format_gwei(max_fee)?, format_gwei(priority_fee)?)
+            }
+            (None, None, Some(gas_price)) => builder.with_gas_price(format_gwei(gas_price)?),
+            _ => match &self.config.fallback_fee_level {
+                Some(fee_level) => builder.with_fee_level(fee_level.clone()),
+                None => builder,
+            },
+        };
+        if let Some(note) = &self.config.note {
+            builder = builder.with_note(note.clone());
+        }
+        let args = builder.build()?;
+
+        // Submit transaction and poll for completion
+        let response = self
+            .fireblocks
+            .create_tx(&args, &RequestOptions::default())
+            .await?;
+        let details = self
+            .wait_for_transaction(&response.id)
+            .await?
+            .into_result()?;
+
+        // Return transaction hash
+        details
+            .tx_hash
+            .parse::<B256>()
+            .map_err(|_| FireblocksError::MissingTxHashError(details.id))
+    }
+
+    /// Resolve the Fireblocks destination for an address.
+    ///
+    /// Cached vault addresses and addresses in the address book are sent to
+    /// as their peer type. Any other address is sent to as a one time
+    /// address, unless those are disabled in the config.
+    pub async fn resolve_destination(
+        &self,
+        to: &Address,
+    ) -> Result<DestinationTransferPeerPath, FireblocksError> {
+        if let Ok(vault_id) = self.get_vault_account_id(to) {
+            return Ok(DestinationTransferPeerPath {
+                peer_type: PeerType::VAULT_ACCOUNT,
+                id: Some(vault_id.to_string()),
+                one_time_address: None,
+            });
+        }
+
+        // Load the address book on first use, and reload it on a miss since
+        // the address may have been whitelisted after it was loaded, though
+        // at most once per refresh interval as one time addresses always miss
+        let refresh_interval = Duration::from_millis(
+            self.config
+                .address_book_refresh_interval
+                .unwrap_or(DEFAULT_ADDRESS_BOOK_REFRESH_INTERVAL),
+        );
+        let (destination, fresh) = self
+            .address_book
+            .read()
+            .map_err(|_| {
+                FireblocksError::SynchronizationError("Failed to acquire read lock".to_string())
+            })?
+            .as_ref()
+            .map_or((None, false), |address_book| {
+                (
+                    address_book.resolve(to),
+                    address_book.loaded_within(refresh_interval),
+                )
+            });
+        let destination = match destination {
+            None if !fresh => self.refresh_address_book().await?.resolve(to),
+            destination => destination,
+        };
+
+        match destination {
+            Some(destination) => Ok(destination),
+            None if self.config.one_time_addresses_enabled.unwrap_or(true) => {
+                Ok(DestinationTransferPeerPath {
+                    peer_type: PeerType::ONE_TIME_ADDRESS,
+                    id: None,
+                    one_time_address: Some(OneTimeAddress {
+                        address: to.to_string(),
+                        tag: None,
+                    }),
+                })
+            }
+            None => Err(FireblocksError::UnresolvedDestinationError(to.to_string())),
+        }
+    }
+
+    /// Reload the address book from the cached vault accounts and the
+    /// whitelisted wallets holding the configured asset
+    pub async fn refresh_address_book(&self) -> Result<AddressBook, FireblocksError> {
+        let asset_id = self
+            .config
+            .asset_id
+            .clone()
+            .ok_or(FireblocksError::MissingAssetIDError())?;
+        let vault_accounts = self
+            .accounts
+            .read()
+            .map_err(|_| {
+                FireblocksError::SynchronizationError("Failed to acquire read lock".to_string())
+            })?
+            .clone();
+
+        let address_book = AddressBook::load(&self.fireblocks, &asset_id, &vault_accounts).await?;
+        *self.address_book.write().map_err(|_| {
+            FireblocksError::SynchronizationError("Failed to acquire write lock".to_string())
+        })? = Some(address_book.clone());
+
+        Ok(address_book)
+    }
+
+    /// Speed up a transaction stuck in the mempool by resubmitting it with
+    /// the same nonce and higher fees through Fireblocks `replaceTxByHash`.
+    ///
+    /// Both transactions are tracked until one of them is mined.
+    pub async fn speed_up_transaction(
+        &self,
+        tx_id: &str,
+        fee_bump: FeeBump,
+    ) -> Result<SpeedUpOutcome, FireblocksError> {
+        let original = self.fireblocks.get_transaction_by_id(tx_id).await?;
+        if !matches!(
+            original.status,
+            TransactionStatus::BROADCASTING | TransactionStatus::CONFIRMING
+        ) {
+            return Err(FireblocksError::TransactionNotReplaceableError(
+                original.id,
+                original.status,
+            ));
+        }
+        if original.tx_hash.is_empty() {
+            return Err(FireblocksError::MissingTxHashError(original.id));
+        }
+
+        let args = replacement_arguments(&original, fee_bump)?;
+        let response = self
+            .fireblocks
+            .create_tx(&args, &RequestOptions::default())
+            .await?;
+        let mined = self
+            .wait_for_replacement(&original.id, &response.id)
+            .await?;
+
+        let mined_tx_hash = mined
+            .tx_hash
+            .parse::<B256>()
+            .map_err(|_| FireblocksError::MissingTxHashError(mined.id.clone()))?;
+        Ok(SpeedUpOutcome {
+            original_tx_id: original.id,
+            replacement_tx_id: response.id,
+            mined_tx_id: mined.id,
+            mined_tx_hash,
+        })
+    }
+
+    /// Poll a stuck transaction and its replacement until one of them
+    /// completes, returning the details of the one that was mined
+    async fn wait_for_replacement(
+        &self,
+        original_tx_id: &str,
+        replacement_tx_id: &str,
+    ) -> Result<TransactionDetails, FireblocksError> {
+        let options = self.config.polling_options();
+        let deadline = options.timeout.map(|timeout| Instant::now() + timeout);
+
+        loop {
+            let replacement = self
+                .fireblocks
+                .get_transaction_by_id(replacement_tx_id)
+                .await?;
+            if replacement.status.is_successful() {
+                return Ok(replacement);
+            }
+
+            let original = self
+                .fireblocks
+                .get_transaction_by_id(original_tx_id)
+                .await?;
+            if original.status.is_successful() {
+                return Ok(original);
+            }
+
+            if replacement.status.is_final() && original.status.is_final() {
+                return Err(FireblocksError::TransactionFailedError(
+                    replacement.id,
+                    replacement.status,
+                    replacement.sub_status,
+                ));
+            }
+
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return Err(FireblocksError::TransactionTimeoutError(
+                    replacement.id,
+                    replacement.status,
+                ));
+            }
+            tokio::time::sleep(options.interval).await;
+        }
+    }
+
+    /// Build a signer for a cached vault address
+    pub fn signer(&self, address: &Address) -> Result<FireblocksSigner, FireblocksError> {
+        let vault_id = self.get_vault_account_id(address)?;
+        let asset_id = self
+            .config
+            .asset_id
+            .clone()
+            .ok_or(FireblocksError::MissingAssetIDError())?;
+
+        let signer = FireblocksSigner::new(self.fireblocks.clone(), vault_id, asset_id, *address)
+            .with_chain_id(Some(self.config.chain_id.clone() as u64))
+            .with_polling_options(self.config.polling_options());
+
+        Ok(match &self.config.note {
+            Some(note) => signer.with_note(note.clone()),
+            None => signer,
+        })
+    }
+
+    /// Build a wallet signing for every cached vault address, with the lowest
+    /// vault account id as the default signer
+    pub fn wallet(&self) -> Result<FireblocksWallet, FireblocksError> {
+        let mut signers = self
+            .get_addresses()?
+            .iter()
+            .map(|address| self.signer(address))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter();
+
+        let default = signers
+            .next()
+            .ok_or(FireblocksError::FailedToPopulateAccountsError())?;
+        Ok(signers.fold(
+            FireblocksWallet::new(default),
+            FireblocksWallet::with_signer,
+        ))
+    }
+
+    /// Sign a message with `personal_sign` semantics via a Fireblocks RAW
+    /// ETH_MESSAGE operation. Fireblocks applies the EIP-191 prefix.
+    pub async fn sign_message(
+        &self,
+        address: &Address,
+        message: &[u8],
+    ) -> Result<PrimitiveSignature, FireblocksError> {
+        self.signer(address)?.sign_eth_message(message).await
+    }
+
+    /// Sign EIP-712 typed data via a Fireblocks RAW EIP712 operation
+    pub async fn sign_typed_data(
+        &self,
+        address: &Address,
+        typed_data: &TypedData,
+    ) -> Result<PrimitiveSignature, FireblocksError> {
+        self.signer(address)?.sign_eip712(typed_data).await
+    }
+
+    /// Sign a `sol!` struct under the given EIP-712 domain
+    pub async fn sign_typed_data_struct<T: SolStruct + Serialize>(
+        &self,
+        address: &Address,
+        payload: &T,
+        domain: &Eip712Domain,
+    ) -> Result<PrimitiveSignature, FireblocksError> {
+        let typed_data = TypedData::from_struct(payload, Some(domain.clone()));
+        self.sign_typed_data(address, &typed_data).await
+    }
+
+    /// Poll a Fireblocks transaction at the configured interval until it
+    /// reaches a final status or the configured polling timeout elapses
+    pub async fn wait_for_transaction(
+        &self,
+        tx_id: &str,
+    ) -> Result<TransactionOutcome, FireblocksError> {
+        self.fireblocks
+            .wait_for_transaction(tx_id, &self.config.polling_options())
+            .await
+    }
+
+    /// EIP-1193 request entry point.
+    ///
+    /// Account and transaction methods are handled by Fireblocks, everything
+    /// else is forwarded to the inner RPC provider.
+    pub async fn request(&self, args: RequestArguments<Value>) -> Result<Value, ProviderRpcError> {
+        match args.method.as_str() {
+            "eth_accounts" | "eth_requestAccounts" => {
+                let addresses = self.get_addresses().map_err(|e| self.rpc_error(e, &args))?;
+                Ok(Value::from(
+                    addresses
+                        .iter()
+                        .map(|address| address.to_string())
+                        .collect::<Vec<_>>(),
+                ))
+            }
+            "eth_sendTransaction" => {
+                let tx = args
+                    .params
+                    .as_ref()
+                    .and_then(|params| params.get(0))
+                    .cloned()
+                    .ok_or_else(|| {
+                        ProviderRpcError::new(
+                            ProviderRpcError::INVALID_PARAMS,
+                            "Missing transaction parameter",
+                            args.clone(),
+                        )
+                    })?;
+                let tx: TransactionRequest = serde_json::from_value(tx).map_err(|e| {
+                    ProviderRpcError::new(
+                        ProviderRpcError::INVALID_PARAMS,
+                        format!("Invalid transaction parameter: {}", e),
+                        args.clone(),
+                    )
+                })?;
+
+                let tx_hash = self
+                    .create_fireblocks_transaction(&tx)
+                    .await
+                    .map_err(|e| self.rpc_error(e, &args))?;
+                Ok(Value::from(tx_hash.to_string()))
+            }
+            "personal_sign" | "eth_sign" => {
+                // personal_sign takes (message, address), eth_sign takes (address, message)
+                let (message_index, address_index) = if args.method == "personal_sign" {
+                    (0, 1)
+                } else {
+                    (1, 0)
+                };
+                let message = message_bytes(string_param(&args, message_index)?);
+                let address = string_param(&args, address_index)?
+                    .parse::<Address>()
+                    .map_err(|e| {
+                        ProviderRpcError::new(
+                            ProviderRpcError::INVALID_PARAMS,
+                            format!("Invalid address parameter: {}", e),
+                            args.clone(),
+                        )
+                    })?;
+
+                let signature = self
+                    .sign_message(&address, &message)
+                    .await
+                    .map_err(|e| self.rpc_error(e, &args))?;
+                Ok(Value::from(hex::encode_prefixed(signature.as_bytes())))
+            }
+            "eth_signTypedData_v3" | "eth_signTypedData_v4" => {
+                let address = string_param(&args, 0)?.parse::<Address>().map_err(|e| {
+                    ProviderRpcError::new(
+                        ProviderRpcError::INVALID_PARAMS,
+                        format!("Invalid address parameter: {}", e),
+                        args.clone(),
+                    )
+                })?;
+
+                // Typed data is usually passed as a JSON string but some clients send the object
+                let typed_data = match args.params.as_ref().and_then(|params| params.get(1)) {
+                    Some(Value::String(typed_data)) => serde_json::from_str(typed_data),
+                    Some(typed_data) => serde_json::from_value(typed_data.clone()),
+                    None => serde_json::from_value(Value::Null),
+                }
+                .map_err(|e: serde_json::Error| {
+                    ProviderRpcError::new(
+                        ProviderRpcError::INVALID_PARAMS,
+                        format!("Invalid typed data parameter: {}", e),
+                        args.clone(),
+                    )
+                })?;
+
+                let signature = self
+                    .sign_typed_data(&address, &typed_data)
+                    .await
+                    .map_err(|e| self.rpc_error(e, &args))?;
+                Ok(Value::from(hex::encode_prefixed(signature.as_bytes())))
+            }
+            "eth_signTypedData" => Err(ProviderRpcError::new(
+                ProviderRpcError::UNSUPPORTED_METHOD,
+                format!(
+                    "Method {} is not supported by the Fireblocks provider",
+                    args.method
+                ),
+                args,
+            )),
+            _ => self.forward_request(args).await,
+        }
+    }
+
+    /// Forward a request to the inner RPC provider
+    async fn forward_request(
+        &self,
+        args: RequestArguments<Value>,
+    ) -> Result<Value, ProviderRpcError> {
+        let params = args.params.clone().unwrap_or_else(|| Value::Array(vec![]));
+        let params = RawValue::from_string(params.to_string()).map_err(|e| {
+            ProviderRpcError::new(
+                ProviderRpcError::INVALID_PARAMS,
+                e.to_string(),
+                args.clone(),
+            )
+        })?;
+
+        let result = self
+            .inner
+            .raw_request_dyn(Cow::Owned(args.method.clone()), &params)
+            .await
+            .map_err(|e| transport_rpc_error(e, &args))?;
+
+        serde_json::from_str(result.get()).map_err(|e| {
+            ProviderRpcError::new(ProviderRpcError::INTERNAL_ERROR, e.to_string(), args)
+        })
+    }
+
+    /// Whether failures are explained in EIP-1193 errors rather than raw
+    /// Fireblocks errors
+    fn enhanced_error_handling(&self) -> bool {
+        self.config.enhanced_error_handling.unwrap_or(true)
+    }
+
+    /// Map a Fireblocks error to an EIP-1193 error
+    fn rpc_error(
+        &self,
+        error: FireblocksError,
+        args: &RequestArguments<Value>,
+    ) -> ProviderRpcError {
+        if self.enhanced_error_handling() {
+            enhanced_rpc_error(error, args, self.config.api_base_url.console_url())
+        } else {
+            fireblocks_rpc_error(error, args)
+        }
+    }
+
+    /// Map a Fireblocks error to a transport error. With enhanced error
+    /// handling it is an error response carrying the EIP-1193 error,
+    /// otherwise the raw Fireblocks error
+    fn transport_error(
+        &self,
+        error: FireblocksError,
+        args: &RequestArguments<Value>,
+    ) -> TransportError {
+        if self.enhanced_error_handling() {
+            RpcError::ErrorResp(self.rpc_error(error, args).into())
+        } else {
+            TransportErrorKind::custom(error)
+        }
+    }
+}
+
+/// Rebuild the arguments of a stuck transaction so it is replaced with the
+/// given fees
+fn replacement_arguments(
+    original: &TransactionDetails,
+    fee_bump: FeeBump,
+) -> Result<TransactionArguments, FireblocksError> {
+    let source = match &original.source {
+        Some(TransferPeerPathResponse {
+            peer_type: PeerType::VAULT_ACCOUNT,
+            id: Some(id),
+            ..
+        }) => TransferPeerPath {
+            peer_type: PeerType::VAULT_ACCOUNT,
+            id: id.clone(),
+        },
+        _ => {
+            return Err(FireblocksError::TransactionNotReplaceableError(
+                original.id.clone(),
+                original.status.clone(),
+            ))
+        }
+    };
+
+    let mut builder = TransactionArguments::builder(
+        original.asset_id.clone(),
+        original
+            .operation
+            .clone()
+            .unwrap_or(TransactionOperation::TRANSFER),
+        source,
+    )
+    .with_replace_tx_by_hash(original.tx_hash.clone());
+
+    if let Some(destination) = &original.destination {
+        builder = match (&destination.peer_type, &original.destination_address) {
+            (PeerType::ONE_TIME_ADDRESS, Some(address)) => {
+                builder.with_one_time_address(address.clone())
+            }
+            (peer_type, _) => builder.with_destination(DestinationTransferPeerPath {
+                peer_type: peer_type.clone(),
+                id: destination.id.clone(),
+                one_time_address: None,
+            }),
+        };
+    }
+
+    let amount = original
+        .amount_info
+        .as_ref()
+        .and_then(|info| info.requested_amount.clone().or(info.amount.clone()));
+    if let Some(amount) = amount {
+        builder = builder.with_amount(amount);
+    }
+
+    let call_data = original
+        .extra_parameters
+        .as_ref()
+        .and_then(|params| params.get("contractCallData"))
+        .and_then(Value::as_str);
+    if let Some(data) = call_data {
+        builder = builder.with_contract_call_data(data.to_string());
+    }
+
+    if let Some(note) = &original.note {
+        builder = builder.with_note(note.clone());
+    }
+
+    match fee_bump {
+        FeeBump::FeeLevel(fee_level) => builder.with_fee_level(fee_level),
+        FeeBump::Eip1559 {
+            max_fee,
+            priority_fee,
+        } => builder.with_eip1559_fees(max_fee, priority_fee),
+    }
+    .build()
+}
+
+/// Format a wei amount as gwei, without trailing zeros
+fn format_gwei(wei: u128) -> Result<String, FireblocksError> {
+    let gwei = format_units(wei, "gwei")
+        .map_err(|e| FireblocksError::InvalidTransactionArgumentsError(e.to_string()))?;
+    Ok(gwei.trim_end_matches('0').trim_end_matches('.').to_string())
+}
+
+/// Get a string parameter by position
+fn string_param(args: &RequestArguments<Value>, index: usize) -> Result<&str, ProviderRpcError> {
+    args.params
+        .as_ref()
+        .and_then(|params| params.get(index))
+        .and_then(Value::as_str)
+        .ok_or_else(|| {
+            ProviderRpcError::new(
+                ProviderRpcError::INVALID_PARAMS,
+                format!("Missing string parameter at position {}", index),
+                args.clone(),
+            )
+        })
+}
+
+/// Decode a message parameter, which is either 0x-prefixed hex or plain text
+fn message_bytes(message: &str) -> Vec<u8> {
+    match message.strip_prefix("0x").map(hex::decode) {
+        Some(Ok(bytes)) => bytes,
+        _ => message.as_bytes().to_vec(),
+    }
+}
+
+/// Map a Fireblocks error to an EIP-1193 error
+fn fireblocks_rpc_error(
+    error: FireblocksError,
+    args: &RequestArguments<Value>,
+) -> ProviderRpcError {
+    let code = match &error {
+        FireblocksError::TransactionFailedError(
+            _,
+            TransactionStatus::REJECTED | TransactionStatus::CANCELLED,
+            _,
+        ) => ProviderRpcError::USER_REJECTED_REQUEST,
+        FireblocksError::UnknownAccountError(_)
+        | FireblocksError::MissingFromAddressError()
+        | FireblocksError::SignatureMismatchError(_) => ProviderRpcError::UNAUTHORIZED,
+        FireblocksError::ChainIdMismatchError(_, _) => ProviderRpcError::CHAIN_DISCONNECTED,
+        FireblocksError::MissingToAddressError() => ProviderRpcError::INVALID_PARAMS,
+        FireblocksError::SendError(_) => ProviderRpcError::DISCONNECTED,
+        _ => ProviderRpcError::INTERNAL_ERROR,
+    };
+    ProviderRpcError::new(code, error.to_string(), args.clone())
+}
+
+/// Map a Fireblocks error to an EIP-1193 error explaining why the request
+/// failed, linking to the transaction in the Fireblocks console if there is one
+fn enhanced_rpc_error(
+    error: FireblocksError,
+    args: &RequestArguments<Value>,
+    console_url: &str,
+) -> ProviderRpcError {
+    match error {
+        FireblocksError::TransactionFailedError(tx_id, status, sub_status) => {
+            let (code, reason) = transaction_failure(&status, &sub_status);
+            let data = serde_json::json!({
+                "txId": tx_id,
+                "status": status,
+                "subStatus": sub_status,
+            });
+            ProviderRpcError::new(
+                code,
+                format!(
+                    "Fireblocks transaction {} {}, see {}",
+                    tx_id,
+                    reason,
+                    console_transaction_url(console_url, &tx_id)
+                ),
+                args.clone(),
+            )
+            .with_data(data.to_string())
+        }
+        FireblocksError::TransactionTimeoutError(tx_id, status) => ProviderRpcError::new(
+            ProviderRpcError::INTERNAL_ERROR,
+            format!(
+                "Fireblocks transaction {} is still {:?} after the polling timeout, see {}",
+                tx_id,
+                status,
+                console_transaction_url(console_url, &tx_id)
+            ),
+            args.clone(),
+        ),
+        FireblocksError::ApiError(api_error) => {
+            let code = match api_error.kind {
+                ApiErrorKind::InsufficientBalance => ProviderRpcError::INVALID_INPUT,
+                ApiErrorKind::PolicyRejection | ApiErrorKind::WhitelistViolation => {
+                    ProviderRpcError::TRANSACTION_REJECTED
+                }
+                ApiErrorKind::UnknownVault => ProviderRpcError::UNAUTHORIZED,
+                ApiErrorKind::InvalidAddress => ProviderRpcError::INVALID_PARAMS,
+                ApiErrorKind::Other => match api_error.status {
+                    StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                        ProviderRpcError::UNAUTHORIZED
+                    }
+                    StatusCode::TOO_MANY_REQUESTS => ProviderRpcError::LIMIT_EXCEEDED,
+                    _ => ProviderRpcError::INTERNAL_ERROR,
+                },
+            };
+            let message = match &api_error.request_id {
+                Some(request_id) => format!(
+                    "Fireblocks rejected the request: {} (request id {})",
+                    api_error.message, request_id
+                ),
+                None => format!("Fireblocks rejected the request: {}", api_error.message),
+            };
+            ProviderRpcError::new(code, message, args.clone())
+        }
+        error => fireblocks_rpc_error(error, args),
+    }
+}
+
+/// Get the EIP-1193 error code of a failed transaction and the reason it
+/// failed, from its sub status when Fireblocks provided one
+fn transaction_failure(status: &TransactionStatus, sub_status: &str) -> (i32, String) {
+    match sub_status {
+        "INSUFFICIENT_FUNDS" | "INSUFFICIENT_FUNDS_FOR_FEE" => (
+            ProviderRpcError::INVALID_INPUT,
+            format!(
+                "failed because the vault account has insufficient funds ({})",
+                sub_status
+            ),
+        ),
+        "CANCELLED_BY_USER" => (
+            ProviderRpcError::USER_REJECTED_REQUEST,
+            "was cancelled by a user".to_string(),
+        ),
+        "REJECTED_BY_USER" => (
+            ProviderRpcError::USER_REJECTED_REQUEST,
+            "was rejected by a signer or approver".to_string(),
+        ),
+        sub_status if sub_status.contains("POLICY") => (
+            ProviderRpcError::TRANSACTION_REJECTED,
+            format!(
+                "was blocked by the Transaction Authorization Policy ({})",
+                sub_status
+            ),
+        ),
+        sub_status if sub_status.contains("AML") || sub_status.contains("SCREENING") => (
+            ProviderRpcError::TRANSACTION_REJECTED,
+            format!("was blocked by AML screening ({})", sub_status),
+        ),
+        _ => {
+            let code = match status {
+                TransactionStatus::REJECTED | TransactionStatus::CANCELLED => {
+                    ProviderRpcError::USER_REJECTED_REQUEST
+                }
+                TransactionStatus::BLOCKED => ProviderRpcError::TRANSACTION_REJECTED,
+                _ => ProviderRpcError::INTERNAL_ERROR,
+            };
+            let reason = if sub_status.is_empty() {
+                format!("failed with status {:?}", status)
+            } else {
+                format!("failed with status {:?} ({})", status, sub_status)
+            };
+            (code, reason)
+        }
+    }
+}
+
+/// Link to a transaction in the Fireblocks console
+fn console_transaction_url(console_url: &str, tx_id: &str) -> String {
+    format!("{}/v2/transactions/{}", console_url, tx_id)
+}
+
+/// Map an inner RPC transport error to an EIP-1193 error
+fn transport_rpc_error(error: TransportError, args: &RequestArguments<Value>) -> ProviderRpcError {
+    match error {
+        RpcError::ErrorResp(payload) => {
+            let error = ProviderRpcError::new(payload.code as i32, payload.message, args.clone());
+            match payload.data {
+                Some(data) => error.with_data(data.get().to_string()),
+                None => error,
+            }
+        }
+        RpcError::Transport(e) => {
+            ProviderRpcError::new(ProviderRpcError::DISCONNECTED, e.to_string(), args.clone())
+        }
+        e => ProviderRpcError::new(
+            ProviderRpcError::INTERNAL_ERROR,
+            e.to_string(),
+            args.clone(),
+        ),
+    }
+}
+
+/// JSON-RPC methods answered through Fireblocks by [`FireblocksProvider::request`]
+/// rather than by the node, which holds no keys
+const FIREBLOCKS_METHODS: &[&str] = &[
+    "eth_accounts",
+    "eth_requestAccounts",
+    "eth_sendTransaction",
+    "personal_sign",
+    "eth_sign",
+    "eth_signTypedData",
+    "eth_signTypedData_v3",
+    "eth_signTypedData_v4",
+];
+
+/// Read calls are delegated to the inner RPC provider while accounts,
+/// signing and transaction submission are routed through Fireblocks
+#[async_trait]
+impl Provider for FireblocksProvider {
+    fn root(&self) -> &RootProvider<BoxTransport> {
+        self.inner.root()
+    }
+
+    async fn raw_request<P, R>(&self, method: Cow<'static, str>, params: P) -> TransportResult<R>
+    where
+        P: RpcParam,
+        R: RpcReturn,
+        Self: Sized,
+    {
+        let params = serde_json::value::to_raw_value(&params).map_err(RpcError::ser_err)?;
+        let result = self.raw_request_dyn(method, &params).await?;
+        serde_json::from_str(result.get()).map_err(|e| RpcError::deser_err(e, result.get()))
+    }
+
+    async fn raw_request_dyn(
+        &self,
+        method: Cow<'static, str>,
+        params: &RawValue,
+    ) -> TransportResult<Box<RawValue>> {
+        if !FIREBLOCKS_METHODS.contains(&method.as_ref()) {
+            return self.inner.raw_request_dyn(method, params).await;
+        }
+
+        let params: Value =
+            serde_json::from_str(params.get()).map_err(|e| RpcError::deser_err(e, params.get()))?;
+        let args = RequestArguments {
+            method: method.into_owned(),
+            params: Some(params).filter(|params| !params.is_null()),
+        };
+        let result = self
+            .request(args)
+            .await
+            .map_err(|e| RpcError::ErrorResp(e.into()))?;
+        serde_json::value::to_raw_value(&result).map_err(RpcError::ser_err)
+    }
+
+    fn get_accounts(&self) -> ProviderCall<BoxTransport, NoParams, Vec<Address>> {
+        ProviderCall::ready(self.get_addresses().map_err(TransportErrorKind::custom))
+    }
+
+    async fn send_transaction_internal(
+        &self,
+        tx: SendableTx<Ethereum>,
+    ) -> TransportResult<PendingTransactionBuilder<BoxTransport, Ethereum>> {
+        match tx {
+            SendableTx::Builder(tx) => {
+                let tx_hash = self.create_fireblocks_transaction(&tx).await.map_err(|e| {
+                    let args = RequestArguments {
+                        method: "eth_sendTransaction".to_string(),
+                        params: serde_json::to_value([&tx]).ok(),
+                    };
+                    self.transport_error(e, &args)
+                })?;
+                Ok(PendingTransactionBuilder::new(self.root().clone(), tx_hash))
+            }
+            // Already signed transactions can be broadcast as is
+            SendableTx::Envelope(_) => self.inner.send_transaction_internal(tx).await,
+        }
+    }
 }
 
 // Helper functions
 pub fn is_final_status(status: &TransactionStatus) -> bool {
-    matches!(
-        status,
-        TransactionStatus::COMPLETED | TransactionStatus::FAILED | TransactionStatus::REJECTED
-    )
+    status.is_final()
 }
 
 pub fn is_successful_status(status: &TransactionStatus) -> bool {
-    matches!(status, TransactionStatus::COMPLETED)
+    status.is_successful()
+}
+
+/// Format the user agent, appending the custom user agent if any
+fn format_user_agent(custom_ua: Option<&str>) -> String {
+    if let Some(custom_ua) = custom_ua {
+        format!(
+            "alloy-fireblocks/{} {}",
+            env!("CARGO_PKG_VERSION"),
+            custom_ua
+        )
+    } else {
+        format!("alloy-fireblocks/{}", env!("CARGO_PKG_VERSION"))
+    }
 }
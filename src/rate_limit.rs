@@ -0,0 +1,160 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use log::debug;
+use reqwest::Method;
+
+use crate::types::{FireblocksError, RateLimit, RateLimits};
+
+/// Class of Fireblocks endpoints sharing a rate limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndpointClass {
+    /// GET requests
+    Read,
+    /// POST requests creating transactions
+    CreateTransaction,
+    /// Any other POST request
+    Write,
+}
+
+impl EndpointClass {
+    /// Classify a request by its method and path
+    pub fn of(method: &Method, path: &str) -> Self {
+        if method == Method::GET {
+            EndpointClass::Read
+        } else if path.split('?').next() == Some("/v1/transactions") {
+            EndpointClass::CreateTransaction
+        } else {
+            EndpointClass::Write
+        }
+    }
+}
+
+/// How a rate limit affected the requests of an endpoint class
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RateLimitMetrics {
+    /// Requests that went through the limiter
+    pub requests: u64,
+    /// Requests that had to wait for a token
+    pub throttled_requests: u64,
+    /// Time spent waiting, summed over all requests
+    pub total_wait: Duration,
+    /// Longest time a single request waited
+    pub max_wait: Duration,
+}
+
+/// Token bucket refilled continuously at the configured rate
+#[derive(Debug)]
+struct TokenBucket {
+    limit: RateLimit,
+    /// Available tokens, negative when requests are waiting for tokens
+    tokens: f64,
+    last_refill: Instant,
+    metrics: RateLimitMetrics,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            tokens: limit.burst as f64,
+            limit,
+            last_refill: Instant::now(),
+            metrics: RateLimitMetrics::default(),
+        }
+    }
+
+    /// Take a token, returning how long to wait until it is actually available
+    fn reserve(&mut self) -> Duration {
+        let now = Instant::now();
+        let refilled =
+            now.duration_since(self.last_refill).as_secs_f64() * self.limit.requests_per_second;
+        self.tokens = (self.tokens + refilled).min(self.limit.burst as f64);
+        self.last_refill = now;
+        self.tokens -= 1.0;
+
+        let wait = if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::try_from_secs_f64(-self.tokens / self.limit.requests_per_second)
+                .unwrap_or(Duration::MAX)
+        };
+
+        self.metrics.requests += 1;
+        if !wait.is_zero() {
+            self.metrics.throttled_requests += 1;
+            self.metrics.total_wait = self.metrics.total_wait.saturating_add(wait);
+            self.metrics.max_wait = self.metrics.max_wait.max(wait);
+        }
+        wait
+    }
+}
+
+/// Client side token bucket rate limiter, with a bucket per endpoint class.
+///
+/// Clones share the same buckets, so one limiter can be handed to several
+/// clients to keep them under a single workspace quota. Requests over the
+/// limit wait for a token instead of failing.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimiter {
+    read: Option<Arc<Mutex<TokenBucket>>>,
+    create_transaction: Option<Arc<Mutex<TokenBucket>>>,
+    write: Option<Arc<Mutex<TokenBucket>>>,
+}
+
+impl RateLimiter {
+    /// Create a limiter enforcing the given limits. Classes without a limit
+    /// are not throttled, and limits that would block every request are
+    /// rejected
+    pub fn new(limits: &RateLimits) -> Result<Self, FireblocksError> {
+        limits.validate()?;
+        let bucket = |limit: &Option<RateLimit>| {
+            limit
+                .clone()
+                .map(|limit| Arc::new(Mutex::new(TokenBucket::new(limit))))
+        };
+        Ok(Self {
+            read: bucket(&limits.read),
+            create_transaction: bucket(&limits.create_transaction),
+            write: bucket(&limits.write),
+        })
+    }
+
+    fn bucket(&self, class: EndpointClass) -> Option<&Arc<Mutex<TokenBucket>>> {
+        match class {
+            EndpointClass::Read => self.read.as_ref(),
+            EndpointClass::CreateTransaction => self.create_transaction.as_ref(),
+            EndpointClass::Write => self.write.as_ref(),
+        }
+    }
+
+    /// Wait until a request of the given class may be sent
+    pub async fn acquire(&self, class: EndpointClass) {
+        let Some(bucket) = self.bucket(class) else {
+            return;
+        };
+        let wait = bucket
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .reserve();
+
+        if !wait.is_zero() {
+            debug!("Throttling {:?} request for {:?}", class, wait);
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Get the metrics of an endpoint class, empty if it is not limited
+    pub fn metrics(&self, class: EndpointClass) -> RateLimitMetrics {
+        self.bucket(class)
+            .map(|bucket| {
+                bucket
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .metrics
+                    .clone()
+            })
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,168 @@
+use std::{future::Future, path::Path, pin::Pin, sync::Arc};
+
+use async_trait::async_trait;
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine,
+};
+use jsonwebtoken::{crypto, Algorithm, EncodingKey};
+use secrecy::{zeroize::Zeroizing, ExposeSecret, SecretString};
+
+use crate::types::FireblocksError;
+
+/// Signs the JWTs authenticating Fireblocks API requests.
+///
+/// Implement it to keep the API private key in a KMS or HSM, the client only
+/// ever hands it the bytes to sign.
+#[async_trait]
+pub trait RequestSigner: Send + Sync {
+    /// Sign the message with RS256, i.e. RSASSA-PKCS1-v1_5 using SHA-256,
+    /// returning the raw signature
+    async fn sign(&self, message: &[u8]) -> Result<Vec<u8>, FireblocksError>;
+
+    /// Identifier of the signing key, sent as the JWT `kid` header when set
+    fn key_id(&self) -> Option<&str>;
+}
+
+/// Signs with an RSA private key held in memory.
+///
+/// The key is parsed once when the signer is created, so an invalid key is
+/// reported there rather than by the first request.
+pub struct RsaKeySigner {
+    /// Key parsed from the PEM
+    encoding_key: EncodingKey,
+    key_id: Option<String>,
+}
+
+impl RsaKeySigner {
+    /// Create a signer from a PEM encoded PKCS#1 or PKCS#8 private key
+    pub fn from_pem(pem: impl Into<SecretString>) -> Result<Self, FireblocksError> {
+        let pem = pem.into();
+        let encoding_key = EncodingKey::from_rsa_pem(pem.expose_secret().as_bytes())
+            .map_err(|e| FireblocksError::PrivateKeyError(e.to_string()))?;
+        // Only the PEM framing is checked when parsing, the RSA key itself
+        // is first read when signing
+        crypto::sign("", &encoding_key, Algorithm::RS256)
+            .map_err(|e| FireblocksError::PrivateKeyError(e.to_string()))?;
+        Ok(Self {
+            encoding_key,
+            key_id: None,
+        })
+    }
+
+    /// Create a signer from a file holding a PEM encoded private key
+    pub fn from_pem_file(path: impl AsRef<Path>) -> Result<Self, FireblocksError> {
+        let path = path.as_ref();
+        let pem = std::fs::read_to_string(path).map_err(|e| {
+            FireblocksError::PrivateKeyError(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        Self::from_pem(pem)
+    }
+
+    /// Create a signer from a DER encoded PKCS#1 RSA private key
+    pub fn from_der(der: &[u8]) -> Result<Self, FireblocksError> {
+        Self::from_pem(der_to_pem("RSA PRIVATE KEY", der))
+    }
+
+    /// Create a signer from a DER encoded PKCS#8 private key
+    pub fn from_pkcs8_der(der: &[u8]) -> Result<Self, FireblocksError> {
+        Self::from_pem(der_to_pem("PRIVATE KEY", der))
+    }
+
+    /// Builder pattern for key id
+    pub fn with_key_id(mut self, key_id: String) -> Self {
+        self.key_id = Some(key_id);
+        self
+    }
+}
+
+impl std::fmt::Debug for RsaKeySigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RsaKeySigner")
+            .field("encoding_key", &"[REDACTED]")
+            .field("key_id", &self.key_id)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl RequestSigner for RsaKeySigner {
+    async fn sign(&self, message: &[u8]) -> Result<Vec<u8>, FireblocksError> {
+        let message = std::str::from_utf8(message)
+            .map_err(|e| FireblocksError::SignJWTError(e.to_string()))?;
+        let signature = crypto::sign(message, &self.encoding_key, Algorithm::RS256)
+            .map_err(|e| FireblocksError::SignJWTError(e.to_string()))?;
+        URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|e| FireblocksError::SignJWTError(e.to_string()))
+    }
+
+    fn key_id(&self) -> Option<&str> {
+        self.key_id.as_deref()
+    }
+}
+
+/// Boxed future returned by an [`ExternalSigner`] callback
+pub type SignatureFuture = Pin<Box<dyn Future<Output = Result<Vec<u8>, FireblocksError>> + Send>>;
+
+/// Signs with a callback, e.g. a call to a KMS or a PKCS#11 session.
+///
+/// For signers with state of their own, implementing [`RequestSigner`]
+/// directly may be simpler.
+#[derive(Clone)]
+pub struct ExternalSigner {
+    sign: Arc<dyn Fn(Vec<u8>) -> SignatureFuture + Send + Sync>,
+    key_id: Option<String>,
+}
+
+impl ExternalSigner {
+    /// Create a signer calling `sign` with every message to sign with RS256
+    pub fn new<F, Fut>(sign: F) -> Self
+    where
+        F: Fn(Vec<u8>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Vec<u8>, FireblocksError>> + Send + 'static,
+    {
+        Self {
+            sign: Arc::new(move |message| Box::pin(sign(message))),
+            key_id: None,
+        }
+    }
+
+    /// Builder pattern for key id
+    pub fn with_key_id(mut self, key_id: String) -> Self {
+        self.key_id = Some(key_id);
+        self
+    }
+}
+
+impl std::fmt::Debug for ExternalSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExternalSigner")
+            .field("key_id", &self.key_id)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl RequestSigner for ExternalSigner {
+    async fn sign(&self, message: &[u8]) -> Result<Vec<u8>, FireblocksError> {
+        (self.sign)(message.to_vec()).await
+    }
+
+    fn key_id(&self) -> Option<&str> {
+        self.key_id.as_deref()
+    }
+}
+
+/// Wrap a DER encoded key in a PEM block with the given label
+fn der_to_pem(label: &str, der: &[u8]) -> String {
+    let encoded = Zeroizing::new(STANDARD.encode(der));
+    let mut pem = format!("-----BEGIN {}-----\n", label);
+    for line in encoded.as_bytes().chunks(64) {
+        // Base64 output is ASCII, so every chunk is valid UTF-8
+        pem.push_str(std::str::from_utf8(line).unwrap_or_default());
+        pem.push('\n');
+    }
+    pem.push_str(&format!("-----END {}-----\n", label));
+    pem
+}
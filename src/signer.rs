@@ -0,0 +1,262 @@
+use std::time::Duration;
+
+use alloy_consensus::SignableTransaction;
+use alloy_core::{
+    dyn_abi::TypedData,
+    primitives::{eip191_hash_message, hex, Address, ChainId, PrimitiveSignature, B256},
+};
+use alloy_network::TxSigner;
+use alloy_signer::{Error, Result, Signer};
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::{
+    api::FireblocksClient,
+    types::{
+        FireblocksError, PeerType, PollingOptions, RawMessageType, RequestOptions,
+        TransactionArguments, TransactionOperation, TransferPeerPath, UnsignedMessage,
+    },
+};
+
+/// An alloy signer backed by a single Fireblocks vault account.
+///
+/// Every signature is produced by submitting a RAW signing transaction and
+/// waiting for Fireblocks to complete it, so the workspace policy must allow
+/// RAW signing for the vault.
+#[derive(Debug, Clone)]
+pub struct FireblocksSigner {
+    /// Fireblocks SDK client
+    client: FireblocksClient,
+    /// Vault account holding the key
+    vault_id: u64,
+    /// Asset used to select the signing key
+    asset_id: String,
+    /// Address of the vault for the asset
+    address: Address,
+    /// Chain id used for EIP-155 replay protection
+    chain_id: Option<ChainId>,
+    /// Note attached to signing transactions
+    note: Option<String>,
+    /// How signing transactions are polled until final
+    polling: PollingOptions,
+}
+
+impl FireblocksSigner {
+    /// Create a new signer for a vault whose address is already known
+    pub fn new(
+        client: FireblocksClient,
+        vault_id: u64,
+        asset_id: String,
+        address: Address,
+    ) -> Self {
+        Self {
+            client,
+            vault_id,
+            asset_id,
+            address,
+            chain_id: None,
+            note: None,
+            polling: PollingOptions::default(),
+        }
+    }
+
+    /// Create a new signer, using the vault's first deposit address for the asset
+    pub async fn from_vault(
+        client: FireblocksClient,
+        vault_id: u64,
+        asset_id: String,
+    ) -> Result<Self, FireblocksError> {
+        let deposit_addresses = client
+            .get_deposit_address(&vault_id.to_string(), &asset_id)
+            .await?;
+        let first_address = deposit_addresses
+            .first()
+            .ok_or_else(|| FireblocksError::UnknownAccountError(vault_id.to_string()))?;
+        let address = first_address
+            .address
+            .parse::<Address>()
+            .map_err(|_| FireblocksError::InvalidAddressError(first_address.address.clone()))?;
+
+        Ok(Self::new(client, vault_id, asset_id, address))
+    }
+
+    /// Builder pattern for chain id
+    pub fn with_chain_id(mut self, chain_id: Option<ChainId>) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
+    /// Builder pattern for note
+    pub fn with_note(mut self, note: String) -> Self {
+        self.note = Some(note);
+        self
+    }
+
+    /// Builder pattern for polling interval
+    pub fn with_polling_interval(mut self, polling_interval: Duration) -> Self {
+        self.polling.interval = polling_interval;
+        self
+    }
+
+    /// Builder pattern for polling options
+    pub fn with_polling_options(mut self, polling: PollingOptions) -> Self {
+        self.polling = polling;
+        self
+    }
+
+    /// Get the vault account id
+    pub fn vault_id(&self) -> u64 {
+        self.vault_id
+    }
+
+    /// Get the asset id
+    pub fn asset_id(&self) -> &str {
+        &self.asset_id
+    }
+
+    /// Submit a RAW signing transaction for a single message and check the
+    /// returned signature recovers to the vault address over `signing_hash`
+    pub async fn sign_raw_message(
+        &self,
+        message: UnsignedMessage,
+        signing_hash: B256,
+    ) -> Result<PrimitiveSignature, FireblocksError> {
+        let mut builder = TransactionArguments::builder(
+            self.asset_id.clone(),
+            TransactionOperation::RAW,
+            TransferPeerPath {
+                peer_type: PeerType::VAULT_ACCOUNT,
+                id: self.vault_id.to_string(),
+            },
+        )
+        .with_raw_messages(vec![message]);
+        if let Some(note) = &self.note {
+            builder = builder.with_note(note.clone());
+        }
+        let args = builder.build()?;
+
+        let response = self
+            .client
+            .create_tx(&args, &RequestOptions::default())
+            .await?;
+        let details = self
+            .client
+            .wait_for_completion(&response.id, &self.polling)
+            .await?;
+        let signature = details
+            .signed_messages
+            .first()
+            .ok_or_else(|| FireblocksError::MissingSignatureError(details.id.clone()))?
+            .signature
+            .to_signature()?;
+
+        // Make sure the vault actually signed for the expected address
+        let signer = signature
+            .recover_address_from_prehash(&signing_hash)
+            .map_err(|e| FireblocksError::InvalidSignatureError(e.to_string()))?;
+        if signer != self.address {
+            return Err(FireblocksError::SignatureMismatchError(
+                self.address.to_string(),
+            ));
+        }
+
+        Ok(signature)
+    }
+
+    /// Sign a message with `personal_sign` semantics via a RAW ETH_MESSAGE
+    /// operation. Fireblocks applies the EIP-191 prefix.
+    pub async fn sign_eth_message(
+        &self,
+        message: &[u8],
+    ) -> Result<PrimitiveSignature, FireblocksError> {
+        self.sign_raw_message(
+            UnsignedMessage {
+                content: Value::from(hex::encode(message)),
+                kind: Some(RawMessageType::ETH_MESSAGE),
+            },
+            eip191_hash_message(message),
+        )
+        .await
+    }
+
+    /// Sign EIP-712 typed data via a RAW EIP712 operation
+    pub async fn sign_eip712(
+        &self,
+        typed_data: &TypedData,
+    ) -> Result<PrimitiveSignature, FireblocksError> {
+        let signing_hash = typed_data
+            .eip712_signing_hash()
+            .map_err(|e| FireblocksError::TypedDataError(e.to_string()))?;
+        let content = serde_json::to_value(typed_data)
+            .map_err(|e| FireblocksError::JSONError(e.to_string()))?;
+
+        self.sign_raw_message(
+            UnsignedMessage {
+                content,
+                kind: Some(RawMessageType::EIP712),
+            },
+            signing_hash,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl Signer for FireblocksSigner {
+    /// Signs the hash as is, without any prefix
+    async fn sign_hash(&self, hash: &B256) -> Result<PrimitiveSignature> {
+        self.sign_raw_message(
+            UnsignedMessage {
+                content: Value::from(hex::encode(hash)),
+                kind: None,
+            },
+            *hash,
+        )
+        .await
+        .map_err(Error::other)
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<PrimitiveSignature> {
+        self.sign_eth_message(message).await.map_err(Error::other)
+    }
+
+    async fn sign_dynamic_typed_data(&self, payload: &TypedData) -> Result<PrimitiveSignature> {
+        self.sign_eip712(payload).await.map_err(Error::other)
+    }
+
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn chain_id(&self) -> Option<ChainId> {
+        self.chain_id
+    }
+
+    fn set_chain_id(&mut self, chain_id: Option<ChainId>) {
+        self.chain_id = chain_id;
+    }
+}
+
+#[async_trait]
+impl TxSigner<PrimitiveSignature> for FireblocksSigner {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx: &mut dyn SignableTransaction<PrimitiveSignature>,
+    ) -> Result<PrimitiveSignature> {
+        if let Some(chain_id) = self.chain_id {
+            if !tx.set_chain_id_checked(chain_id) {
+                return Err(Error::TransactionChainIdMismatch {
+                    signer: chain_id,
+                    // we can only end up here if the tx has a chain id
+                    tx: tx.chain_id().unwrap(),
+                });
+            }
+        }
+
+        self.sign_hash(&tx.signature_hash()).await
+    }
+}
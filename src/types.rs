@@ -1,9 +1,16 @@
 #![allow(dead_code, non_camel_case_types)]
 
-use std::{borrow::Borrow, num::ParseIntError};
+use std::{borrow::Borrow, num::ParseIntError, time::Duration};
 
+use alloy_core::primitives::{PrimitiveSignature, B256, U256};
+use alloy_json_rpc::ErrorPayload;
+use rand::Rng;
 use reqwest::StatusCode;
-use serde::{Deserialize, Serialize};
+use secrecy::{ExposeSecret, SecretString};
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::value::RawValue;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
 
 use thiserror::Error;
 
@@ -49,8 +56,78 @@ pub struct PagedVaultAccountsResponse {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Paging {
-    before: Option<String>,
-    after: Option<String>,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// Filters for listing vault accounts, serialized as query parameters
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultAccountsFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name_prefix: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name_suffix: Option<String>,
+    /// Only vaults holding at least this amount of `asset_id`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_amount_threshold: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_by: Option<SortDirection>,
+    /// Cursor of the page to fetch, taken from the previous page's paging
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// Cursor of the page to fetch, taken from the previous page's paging
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// Maximum number of vaults per page, at most 500
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+impl VaultAccountsFilter {
+    /// Builder pattern for name prefix
+    pub fn with_name_prefix(mut self, name_prefix: String) -> Self {
+        self.name_prefix = Some(name_prefix);
+        self
+    }
+
+    /// Builder pattern for name suffix
+    pub fn with_name_suffix(mut self, name_suffix: String) -> Self {
+        self.name_suffix = Some(name_suffix);
+        self
+    }
+
+    /// Builder pattern for minimum amount threshold
+    pub fn with_min_amount_threshold(mut self, min_amount_threshold: f64) -> Self {
+        self.min_amount_threshold = Some(min_amount_threshold);
+        self
+    }
+
+    /// Builder pattern for asset id
+    pub fn with_asset_id(mut self, asset_id: String) -> Self {
+        self.asset_id = Some(asset_id);
+        self
+    }
+
+    /// Builder pattern for ordering
+    pub fn with_order_by(mut self, order_by: SortDirection) -> Self {
+        self.order_by = Some(order_by);
+        self
+    }
+
+    /// Builder pattern for page size
+    pub fn with_limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Builder pattern for the page cursor
+    pub fn with_after(mut self, after: String) -> Self {
+        self.after = Some(after);
+        self
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -119,11 +196,65 @@ pub struct AssetWalletsResponse {
     #[serde(rename = "lockedAmount")]
     pub locked_amount: String,
     #[serde(rename = "blockHeight")]
-    pub block_height: String,
+    pub block_height: Option<String>,
     #[serde(rename = "blockHash")]
-    pub block_hash: String,
-    #[serde(rename = "creationTime")]
-    pub creation_time: String,
+    pub block_hash: Option<String>,
+    #[serde(rename = "creationTimestamp", alias = "creationTime")]
+    pub creation_time: Option<String>,
+}
+
+/// Filters for listing asset wallets, serialized as query parameters
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetWalletsFilter {
+    /// Only wallets whose total balance is larger than this amount
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_amount_larger_than: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_by: Option<SortDirection>,
+    /// Cursor of the page to fetch, taken from the previous page's paging
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// Cursor of the page to fetch, taken from the previous page's paging
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// Maximum number of wallets per page, at most 1000
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+impl AssetWalletsFilter {
+    /// Builder pattern for total amount threshold
+    pub fn with_total_amount_larger_than(mut self, total_amount_larger_than: f64) -> Self {
+        self.total_amount_larger_than = Some(total_amount_larger_than);
+        self
+    }
+
+    /// Builder pattern for asset id
+    pub fn with_asset_id(mut self, asset_id: String) -> Self {
+        self.asset_id = Some(asset_id);
+        self
+    }
+
+    /// Builder pattern for ordering
+    pub fn with_order_by(mut self, order_by: SortDirection) -> Self {
+        self.order_by = Some(order_by);
+        self
+    }
+
+    /// Builder pattern for page size
+    pub fn with_limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Builder pattern for the page cursor
+    pub fn with_after(mut self, after: String) -> Self {
+        self.after = Some(after);
+        self
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -149,7 +280,7 @@ pub struct Input {
 pub struct GetAssetWalletsResponse {
     #[serde(rename = "assetWallets")]
     pub asset_wallets: Vec<AssetWalletsResponse>,
-    pub paging: Paging,
+    pub paging: Option<Paging>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -170,126 +301,1048 @@ pub struct DepositAddressResponse {
     pub address_format: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct TransactionArguments {
-    #[serde(rename = "assetId")]
-    pub asset_id: String,
-    pub operation: TransactionOperation,
-    pub source: TransferPeerPath,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub destination: Option<DestinationTransferPeerPath>,
-    pub amount: String,
-    //pub extra_parameters: Option<ExtraParameters>,
-    //#[serde(skip_serializing_if = "Option::is_none")]
-    //pub gas_price: Option<String>,
-    //#[serde(skip_serializing_if = "Option::is_none")]
-    //pub gas_limit: Option<String>,
-    pub note: String,
-}
+/// Request body for creating a deposit address
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAddressRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer_ref_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAddressResponse {
+    pub address: String,
+    pub legacy_address: Option<String>,
+    pub enterprise_address: Option<String>,
+    pub tag: Option<String>,
+    pub bip44_address_index: Option<u64>,
+}
+
+/// A whitelisted internal, external or contract wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletContainerResponse {
+    pub id: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer_ref_id: Option<String>,
+    #[serde(default)]
+    pub assets: Vec<WalletAssetResponse>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletAssetResponse {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activation_time: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionArguments {
+    #[serde(rename = "assetId")]
+    pub asset_id: String,
+    pub operation: TransactionOperation,
+    pub source: TransferPeerPath,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination: Option<DestinationTransferPeerPath>,
+    /// Multiple outputs, for assets that support them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destinations: Option<Vec<TransactionDestination>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<String>,
+    /// Deduct the fee from the amount instead of adding it on top
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub treat_as_gross_amount: Option<bool>,
+    /// Sweep the full balance, for assets with a minimum reserve
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force_sweep: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extra_parameters: Option<ExtraParameters>,
+
+    // Fees
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_level: Option<FeeLevel>,
+    /// Fail instead of broadcasting if the fee is too low
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fail_on_low_fee: Option<bool>,
+    /// UTXO fee per byte or Ripple fee
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee: Option<String>,
+    /// Total network fee, for assets priced per transaction
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network_fee: Option<String>,
+    /// Legacy EVM gas price, in gwei
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<String>,
+    /// EIP-1559 max fee per gas, in gwei
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee: Option<String>,
+    /// EIP-1559 max priority fee per gas, in gwei
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority_fee: Option<String>,
+    /// Hash of a stuck EVM transaction this transaction replaces
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replace_tx_by_hash: Option<String>,
+
+    // References
+    /// Unique id used to deduplicate transaction creation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_tx_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer_ref_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl TransactionArguments {
+    /// Start building transaction arguments from the mandatory fields
+    pub fn builder(
+        asset_id: String,
+        operation: TransactionOperation,
+        source: TransferPeerPath,
+    ) -> TransactionArgumentsBuilder {
+        TransactionArgumentsBuilder::new(asset_id, operation, source)
+    }
+}
+
+/// Builder for [`TransactionArguments`], validating that fee and destination
+/// options are not mixed
+#[derive(Debug)]
+pub struct TransactionArgumentsBuilder {
+    args: TransactionArguments,
+}
+
+impl TransactionArgumentsBuilder {
+    /// Create a new builder from the mandatory fields
+    pub fn new(
+        asset_id: String,
+        operation: TransactionOperation,
+        source: TransferPeerPath,
+    ) -> Self {
+        Self {
+            args: TransactionArguments {
+                asset_id,
+                operation,
+                source,
+                destination: None,
+                destinations: None,
+                amount: None,
+                treat_as_gross_amount: None,
+                force_sweep: None,
+                extra_parameters: None,
+                fee_level: None,
+                fail_on_low_fee: None,
+                fee: None,
+                network_fee: None,
+                gas_price: None,
+                gas_limit: None,
+                max_fee: None,
+                priority_fee: None,
+                replace_tx_by_hash: None,
+                external_tx_id: None,
+                customer_ref_id: None,
+                note: None,
+            },
+        }
+    }
+
+    /// Builder pattern for destination
+    pub fn with_destination(mut self, destination: DestinationTransferPeerPath) -> Self {
+        self.args.destination = Some(destination);
+        self
+    }
+
+    /// Builder pattern for a one time address destination
+    pub fn with_one_time_address(self, address: String) -> Self {
+        self.with_destination(DestinationTransferPeerPath {
+            peer_type: PeerType::ONE_TIME_ADDRESS,
+            id: None,
+            one_time_address: Some(OneTimeAddress { address, tag: None }),
+        })
+    }
+
+    /// Builder pattern for multiple destinations
+    pub fn with_destinations(mut self, destinations: Vec<TransactionDestination>) -> Self {
+        self.args.destinations = Some(destinations);
+        self
+    }
+
+    /// Builder pattern for amount
+    pub fn with_amount(mut self, amount: String) -> Self {
+        self.args.amount = Some(amount);
+        self
+    }
+
+    /// Builder pattern for treat as gross amount
+    pub fn with_treat_as_gross_amount(mut self, treat_as_gross_amount: bool) -> Self {
+        self.args.treat_as_gross_amount = Some(treat_as_gross_amount);
+        self
+    }
+
+    /// Builder pattern for force sweep
+    pub fn with_force_sweep(mut self, force_sweep: bool) -> Self {
+        self.args.force_sweep = Some(force_sweep);
+        self
+    }
+
+    /// Builder pattern for extra parameters
+    pub fn with_extra_parameters(mut self, extra_parameters: ExtraParameters) -> Self {
+        self.args.extra_parameters = Some(extra_parameters);
+        self
+    }
+
+    /// Builder pattern for hex encoded contract call data
+    pub fn with_contract_call_data(self, data: String) -> Self {
+        self.with_extra_parameters(ExtraParameters::ContractCallData(data))
+    }
+
+    /// Builder pattern for raw messages to sign
+    pub fn with_raw_messages(self, messages: Vec<UnsignedMessage>) -> Self {
+        self.with_extra_parameters(ExtraParameters::RawMessageData(RawMessageData { messages }))
+    }
+
+    /// Builder pattern for fee level
+    pub fn with_fee_level(mut self, fee_level: FeeLevel) -> Self {
+        self.args.fee_level = Some(fee_level);
+        self
+    }
+
+    /// Builder pattern for fail on low fee
+    pub fn with_fail_on_low_fee(mut self, fail_on_low_fee: bool) -> Self {
+        self.args.fail_on_low_fee = Some(fail_on_low_fee);
+        self
+    }
+
+    /// Builder pattern for fee
+    pub fn with_fee(mut self, fee: String) -> Self {
+        self.args.fee = Some(fee);
+        self
+    }
+
+    /// Builder pattern for network fee
+    pub fn with_network_fee(mut self, network_fee: String) -> Self {
+        self.args.network_fee = Some(network_fee);
+        self
+    }
+
+    /// Builder pattern for legacy gas price, in gwei
+    pub fn with_gas_price(mut self, gas_price: String) -> Self {
+        self.args.gas_price = Some(gas_price);
+        self
+    }
+
+    /// Builder pattern for gas limit
+    pub fn with_gas_limit(mut self, gas_limit: String) -> Self {
+        self.args.gas_limit = Some(gas_limit);
+        self
+    }
+
+    /// Builder pattern for EIP-1559 fees, in gwei
+    pub fn with_eip1559_fees(mut self, max_fee: String, priority_fee: String) -> Self {
+        self.args.max_fee = Some(max_fee);
+        self.args.priority_fee = Some(priority_fee);
+        self
+    }
+
+    /// Builder pattern for replace tx by hash
+    pub fn with_replace_tx_by_hash(mut self, tx_hash: String) -> Self {
+        self.args.replace_tx_by_hash = Some(tx_hash);
+        self
+    }
+
+    /// Builder pattern for external tx id
+    pub fn with_external_tx_id(mut self, external_tx_id: String) -> Self {
+        self.args.external_tx_id = Some(external_tx_id);
+        self
+    }
+
+    /// Builder pattern for customer ref id
+    pub fn with_customer_ref_id(mut self, customer_ref_id: String) -> Self {
+        self.args.customer_ref_id = Some(customer_ref_id);
+        self
+    }
+
+    /// Builder pattern for note
+    pub fn with_note(mut self, note: String) -> Self {
+        self.args.note = Some(note);
+        self
+    }
+
+    /// Validate and build the transaction arguments
+    pub fn build(self) -> Result<TransactionArguments, FireblocksError> {
+        let args = self.args;
+        if args.destination.is_some() && args.destinations.is_some() {
+            return Err(FireblocksError::InvalidTransactionArgumentsError(
+                "destination and destinations are mutually exclusive".to_string(),
+            ));
+        }
+
+        let explicit_fees = args.gas_price.is_some()
+            || args.max_fee.is_some()
+            || args.priority_fee.is_some()
+            || args.fee.is_some()
+            || args.network_fee.is_some();
+        if args.fee_level.is_some() && explicit_fees {
+            return Err(FireblocksError::InvalidTransactionArgumentsError(
+                "fee level cannot be combined with explicit fees".to_string(),
+            ));
+        }
+        if args.gas_price.is_some() && (args.max_fee.is_some() || args.priority_fee.is_some()) {
+            return Err(FireblocksError::InvalidTransactionArgumentsError(
+                "gas price cannot be combined with EIP-1559 fees".to_string(),
+            ));
+        }
+
+        Ok(args)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionDestination {
+    pub amount: String,
+    pub destination: DestinationTransferPeerPath,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExtraParameters {
+    ContractCallData(String),
+    RawMessageData(RawMessageData),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferPeerPath {
+    #[serde(rename = "type")]
+    pub peer_type: PeerType,
+    pub id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DestinationTransferPeerPath {
+    #[serde(rename = "type")]
+    pub peer_type: PeerType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub one_time_address: Option<OneTimeAddress>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OneTimeAddress {
+    pub address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum TransactionOperation {
+    TRANSFER,
+    RAW,
+    CONTRACT_CALL,
+
+    MINT,
+    BURN,
+    SUPPLY_TO_COMPOUND,
+    REDEEM_FROM_COMPOUND,
+    TYPED_MESSAGE,
+    ENABLE_ASSET,
+    STAKE,
+    UNSTAKE,
+    WITHDRAW,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum PeerType {
+    VAULT_ACCOUNT,
+    EXCHANGE_ACCOUNT,
+    INTERNAL_WALLET,
+    EXTERNAL_WALLET,
+    ONE_TIME_ADDRESS,
+    NETWORK_CONNECTION,
+    FIAT_ACCOUNT,
+    COMPOUND,
+    GAS_STATION,
+    END_USER_WALLET,
+    OEC_PARTNER,
+    CONTRACT,
+    UNKNOWN,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTransactionResponse {
+    pub id: String,
+    pub status: TransactionStatus,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionStatus {
+    SUBMITTED,
+    QUEUED,
+    PENDING_SIGNATURE,
+    PENDING_AUTHORIZATION,
+    PENDING_3RD_PARTY_MANUAL_APPROVAL,
+    PENDING_3RD_PARTY,
+    PENDING,
+    BROADCASTING,
+    CONFIRMING,
+    CONFIRMED,
+    COMPLETED,
+    PENDING_AML_SCREENING,
+    PARTIALLY_COMPLETED,
+    CANCELLING,
+    CANCELLED,
+    REJECTED,
+    FAILED,
+    TIMEOUT,
+    BLOCKED,
+}
+
+impl TransactionStatus {
+    /// Whether the transaction can no longer change status
+    pub fn is_final(&self) -> bool {
+        self.is_successful() || self.is_failed()
+    }
+
+    /// Whether the transaction reached its final, successful status
+    pub fn is_successful(&self) -> bool {
+        matches!(self, TransactionStatus::COMPLETED)
+    }
+
+    /// Whether the transaction reached a final status without completing
+    pub fn is_failed(&self) -> bool {
+        match self {
+            TransactionStatus::PARTIALLY_COMPLETED
+            | TransactionStatus::CANCELLED
+            | TransactionStatus::REJECTED
+            | TransactionStatus::FAILED
+            | TransactionStatus::TIMEOUT
+            | TransactionStatus::BLOCKED => true,
+            TransactionStatus::SUBMITTED
+            | TransactionStatus::QUEUED
+            | TransactionStatus::PENDING_SIGNATURE
+            | TransactionStatus::PENDING_AUTHORIZATION
+            | TransactionStatus::PENDING_3RD_PARTY_MANUAL_APPROVAL
+            | TransactionStatus::PENDING_3RD_PARTY
+            | TransactionStatus::PENDING
+            | TransactionStatus::BROADCASTING
+            | TransactionStatus::CONFIRMING
+            | TransactionStatus::CONFIRMED
+            | TransactionStatus::COMPLETED
+            | TransactionStatus::PENDING_AML_SCREENING
+            | TransactionStatus::CANCELLING => false,
+        }
+    }
+}
+
+/// Final outcome of a transaction that was polled until it stopped changing
+#[derive(Debug)]
+pub enum TransactionOutcome {
+    /// The transaction completed successfully
+    Completed(TransactionDetails),
+    /// The transaction was cancelled, rejected, blocked, timed out or failed
+    Failed(TransactionDetails),
+}
+
+impl TransactionOutcome {
+    /// Classify final transaction details
+    pub fn from_details(details: TransactionDetails) -> Self {
+        if details.status.is_successful() {
+            TransactionOutcome::Completed(details)
+        } else {
+            TransactionOutcome::Failed(details)
+        }
+    }
+
+    /// Get the final transaction details
+    pub fn details(&self) -> &TransactionDetails {
+        match self {
+            TransactionOutcome::Completed(details) | TransactionOutcome::Failed(details) => details,
+        }
+    }
+
+    /// Get the final transaction status
+    pub fn status(&self) -> &TransactionStatus {
+        &self.details().status
+    }
+
+    /// Get the sub status explaining a failure, if Fireblocks provided one
+    pub fn sub_status(&self) -> Option<&str> {
+        match self {
+            TransactionOutcome::Failed(details) if !details.sub_status.is_empty() => {
+                Some(details.sub_status.as_str())
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the transaction completed successfully
+    pub fn is_success(&self) -> bool {
+        matches!(self, TransactionOutcome::Completed(_))
+    }
+
+    /// Get the details of a completed transaction, or an error describing the failure
+    pub fn into_result(self) -> Result<TransactionDetails, FireblocksError> {
+        match self {
+            TransactionOutcome::Completed(details) => Ok(details),
+            TransactionOutcome::Failed(details) => Err(FireblocksError::TransactionFailedError(
+                details.id,
+                details.status,
+                details.sub_status,
+            )),
+        }
+    }
+}
+
+/// Controls how a transaction is polled until it reaches a final status
+#[derive(Debug, Clone)]
+pub struct PollingOptions {
+    /// Interval between status requests
+    pub interval: Duration,
+    /// Overall deadline, waits indefinitely only if explicitly unset
+    pub timeout: Option<Duration>,
+    /// Log every observed status change at info level
+    pub log_status_changes: bool,
+}
+
+impl Default for PollingOptions {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(1000),
+            timeout: Some(Duration::from_millis(DEFAULT_POLLING_TIMEOUT)),
+            log_status_changes: false,
+        }
+    }
+}
+
+impl PollingOptions {
+    /// Builder pattern for interval
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Builder pattern for timeout, `None` waits indefinitely
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Builder pattern for status change logging
+    pub fn with_log_status_changes(mut self, log_status_changes: bool) -> Self {
+        self.log_status_changes = log_status_changes;
+        self
+    }
+}
+
+/// Controls how failed Fireblocks API calls are retried.
+///
+/// Network errors, 429 and 5xx gateway responses are retried. GET requests
+/// are always retried, POST requests only when they carry an idempotency key.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled on every following retry
+    pub initial_backoff: Duration,
+    /// Upper bound of the backoff delay, also capping the delay a server
+    /// asks for with Retry-After
+    pub max_backoff: Duration,
+    /// Randomize each delay between half and all of the backoff
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(10),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries
+    pub fn none() -> Self {
+        Self::default().with_max_attempts(1)
+    }
+
+    /// Builder pattern for max attempts
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Builder pattern for initial backoff
+    pub fn with_initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Builder pattern for max backoff
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Builder pattern for jitter
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Delay before retrying after the given failed attempt, starting at 1
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff);
+        if self.jitter {
+            rand::thread_rng().gen_range(backoff / 2..=backoff)
+        } else {
+            backoff
+        }
+    }
+
+    /// Whether a response with this status is worth retrying
+    pub fn is_retryable_status(status: StatusCode) -> bool {
+        matches!(
+            status,
+            StatusCode::TOO_MANY_REQUESTS
+                | StatusCode::INTERNAL_SERVER_ERROR
+                | StatusCode::BAD_GATEWAY
+                | StatusCode::SERVICE_UNAVAILABLE
+                | StatusCode::GATEWAY_TIMEOUT
+        )
+    }
+}
+
+/// A token bucket limit: up to `burst` requests at once, refilled at
+/// `requests_per_second`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimit {
+    pub requests_per_second: f64,
+    pub burst: u32,
+}
+
+impl RateLimit {
+    /// Create a new limit, failing if it would never let a request through
+    pub fn new(requests_per_second: f64, burst: u32) -> Result<Self, FireblocksError> {
+        let limit = Self {
+            requests_per_second,
+            burst,
+        };
+        limit.validate()?;
+        Ok(limit)
+    }
+
+    /// Check the rate is a positive number and the burst at least one request
+    pub fn validate(&self) -> Result<(), FireblocksError> {
+        if !(self.requests_per_second.is_finite() && self.requests_per_second > 0.0) {
+            return Err(FireblocksError::ConfigError(
+                "requests_per_second".to_string(),
+                format!(
+                    "must be a positive number, got {}",
+                    self.requests_per_second
+                ),
+            ));
+        }
+        if self.burst == 0 {
+            return Err(FireblocksError::ConfigError(
+                "burst".to_string(),
+                "must be at least 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Rate limits per endpoint class, unset classes are not limited
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RateLimits {
+    /// Limit of GET requests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read: Option<RateLimit>,
+    /// Limit of requests creating transactions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub create_transaction: Option<RateLimit>,
+    /// Limit of any other POST request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub write: Option<RateLimit>,
+}
+
+impl RateLimits {
+    /// Builder pattern for read limit
+    pub fn with_read(mut self, read: RateLimit) -> Self {
+        self.read = Some(read);
+        self
+    }
+
+    /// Builder pattern for transaction creation limit
+    pub fn with_create_transaction(mut self, create_transaction: RateLimit) -> Self {
+        self.create_transaction = Some(create_transaction);
+        self
+    }
+
+    /// Builder pattern for write limit
+    pub fn with_write(mut self, write: RateLimit) -> Self {
+        self.write = Some(write);
+        self
+    }
+
+    /// Check every limit that is set, naming the class of an invalid one
+    pub fn validate(&self) -> Result<(), FireblocksError> {
+        let classes = [
+            ("read", &self.read),
+            ("create_transaction", &self.create_transaction),
+            ("write", &self.write),
+        ];
+        for (class, limit) in classes {
+            if let Some(limit) = limit {
+                limit.validate().map_err(|e| match e {
+                    FireblocksError::ConfigError(key, message) => {
+                        FireblocksError::ConfigError(format!("{}.{}", class, key), message)
+                    }
+                    e => e,
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionDetails {
+    pub id: String,
+    pub asset_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_tx_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation: Option<TransactionOperation>,
+
+    #[serde(default)]
+    pub tx_hash: String,
+    pub status: TransactionStatus,
+    #[serde(default)]
+    pub sub_status: String,
+
+    // Source and destination
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<TransferPeerPathResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination: Option<TransferPeerPathResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination_address_description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination_tag: Option<String>,
+
+    // Amounts and fees
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount_info: Option<AmountInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_info: Option<FeeInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_currency: Option<String>,
+
+    // Block info and timestamps
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_info: Option<BlockInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_of_confirmations: Option<u64>,
+    /// Unix timestamp in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<u64>,
+    /// Unix timestamp in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<u64>,
+
+    // Approvals
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
+    #[serde(default)]
+    pub signed_by: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rejected_by: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer_ref_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replaced_tx_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extra_parameters: Option<serde_json::Value>,
+
+    #[serde(default)]
+    pub signed_messages: Vec<SignedMessageResponse>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferPeerPathResponse {
+    #[serde(rename = "type")]
+    pub peer_type: PeerType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmountInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requested_amount: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub net_amount: Option<String>,
+    #[serde(rename = "amountUSD")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount_usd: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network_fee: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_fee: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_height: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<String>,
+}
+
+/// Filters for listing transactions, serialized as query parameters
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionFilters {
+    /// Only transactions created before this Unix timestamp in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<u64>,
+    /// Only transactions created after this Unix timestamp in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<u64>,
+    #[serde(serialize_with = "serialize_comma_separated")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub status: Vec<TransactionStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_by: Option<TransactionOrderBy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<SortDirection>,
+    /// Maximum number of transactions returned, at most 500
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_type: Option<PeerType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dest_type: Option<PeerType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dest_id: Option<String>,
+    #[serde(serialize_with = "serialize_comma_separated")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub assets: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    /// Path of the page to fetch from the `next-page` header of the previous
+    /// one, which already holds the other filters
+    #[serde(skip)]
+    pub next_page: Option<String>,
+}
+
+impl TransactionFilters {
+    /// Builder pattern for created before
+    pub fn with_before(mut self, before: u64) -> Self {
+        self.before = Some(before);
+        self
+    }
+
+    /// Builder pattern for created after
+    pub fn with_after(mut self, after: u64) -> Self {
+        self.after = Some(after);
+        self
+    }
+
+    /// Builder pattern for statuses
+    pub fn with_status(mut self, status: Vec<TransactionStatus>) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Builder pattern for ordering
+    pub fn with_order(mut self, order_by: TransactionOrderBy, sort: SortDirection) -> Self {
+        self.order_by = Some(order_by);
+        self.sort = Some(sort);
+        self
+    }
+
+    /// Builder pattern for limit
+    pub fn with_limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Builder pattern for source
+    pub fn with_source(mut self, source_type: PeerType, source_id: Option<String>) -> Self {
+        self.source_type = Some(source_type);
+        self.source_id = source_id;
+        self
+    }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub enum ExtraParameters {
-    ContractCallData(String),
-    RawMessageData(RawMessageData),
-}
+    /// Builder pattern for destination
+    pub fn with_destination(mut self, dest_type: PeerType, dest_id: Option<String>) -> Self {
+        self.dest_type = Some(dest_type);
+        self.dest_id = dest_id;
+        self
+    }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct TransferPeerPath {
-    #[serde(rename = "type")]
-    pub peer_type: PeerType,
-    pub id: String,
-}
+    /// Builder pattern for assets
+    pub fn with_assets(mut self, assets: Vec<String>) -> Self {
+        self.assets = assets;
+        self
+    }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct DestinationTransferPeerPath {
-    #[serde(rename = "type")]
-    pub peer_type: PeerType,
-    //  #[serde(skip_serializing_if = "Option::is_none")]
-    pub id: String,
-    //  #[serde(skip_serializing_if = "Option::is_none")]
-    //  pub one_time_address: Option<OneTimeAddress>,
-}
+    /// Builder pattern for tx hash
+    pub fn with_tx_hash(mut self, tx_hash: String) -> Self {
+        self.tx_hash = Some(tx_hash);
+        self
+    }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct OneTimeAddress {
-    pub address: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub tag: Option<String>,
+    /// Builder pattern for next page
+    pub fn with_next_page(mut self, next_page: String) -> Self {
+        self.next_page = Some(next_page);
+        self
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[allow(clippy::upper_case_acronyms)]
-pub enum TransactionOperation {
-    TRANSFER,
-    RAW,
-    CONTRACT_CALL,
+/// Serialize a list as the comma separated string expected by list filters
+fn serialize_comma_separated<S, T>(values: &[T], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    T: Serialize,
+{
+    let values = values
+        .iter()
+        .map(|value| match serde_json::to_value(value) {
+            Ok(serde_json::Value::String(value)) => Ok(value),
+            Ok(value) => Ok(value.to_string()),
+            Err(e) => Err(serde::ser::Error::custom(e)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    serializer.serialize_str(&values.join(","))
+}
 
-    MINT,
-    BURN,
-    SUPPLY_TO_COMPOUND,
-    REDEEM_FROM_COMPOUND,
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TransactionOrderBy {
+    #[serde(rename = "createdAt")]
+    CreatedAt,
+    #[serde(rename = "lastUpdated")]
+    LastUpdated,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
 #[allow(clippy::upper_case_acronyms)]
-pub enum PeerType {
-    VAULT_ACCOUNT,
-    EXCHANGE_ACCOUNT,
-    INTERNAL_WALLET,
-    EXTERNAL_WALLET,
-    ONE_TIME_ADDRESS,
-    NETWORK_CONNECTION,
-    FIAT_ACCOUNT,
-    COMPOUND,
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SortDirection {
+    ASC,
+    DESC,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct CreateTransactionResponse {
-    pub id: String,
-    pub status: TransactionStatus,
+pub struct OperationSuccessResponse {
+    pub success: bool,
 }
 
-#[allow(clippy::upper_case_acronyms)]
-#[derive(Debug, Serialize, Deserialize)]
-pub enum TransactionStatus {
-    SUBMITTED,
-    QUEUED,
-    PENDING_SIGNATURE,
-    PENDING_AUTHORIZATION,
-    PENDING_3RD_PARTY_MANUAL_APPROVAL,
-    PENDING_3RD_PARTY,
-    PENDING,
-    BROADCASTING,
-    CONFIRMING,
-    CONFIRMED,
-    COMPLETED,
-    PENDING_AML_SCREENING,
-    PARTIALLY_COMPLETED,
-    CANCELLING,
-    CANCELLED,
-    REJECTED,
-    FAILED,
-    TIMEOUT,
-    BLOCKED,
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DropTransactionRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_level: Option<FeeLevel>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct TransactionDetails {
-    pub id: String,
-    pub asset_id: String,
+pub struct DropTransactionResponse {
+    #[serde(default)]
+    pub success: bool,
+    /// Ids of the transactions created to drop the original one
+    #[serde(default)]
+    pub transactions: Vec<String>,
+}
 
-    pub tx_hash: String,
-    pub status: TransactionStatus,
-    pub sub_status: String,
+/// Fees used to resubmit a stuck EVM transaction
+#[derive(Debug, Clone)]
+pub enum FeeBump {
+    /// Let Fireblocks price the replacement at the given fee level
+    FeeLevel(FeeLevel),
+    /// Explicit EIP-1559 fees, in gwei
+    Eip1559 {
+        max_fee: String,
+        priority_fee: String,
+    },
+}
 
-    pub signed_messages: Vec<SignedMessageResponse>,
+/// Result of speeding up a stuck transaction
+#[derive(Debug, Clone)]
+pub struct SpeedUpOutcome {
+    /// Fireblocks id of the stuck transaction
+    pub original_tx_id: String,
+    /// Fireblocks id of the replacement transaction
+    pub replacement_tx_id: String,
+    /// Fireblocks id of the transaction that was mined
+    pub mined_tx_id: String,
+    /// On-chain hash of the transaction that was mined
+    pub mined_tx_hash: B256,
+}
+
+impl SpeedUpOutcome {
+    /// Whether the replacement, rather than the original, was mined
+    pub fn replaced(&self) -> bool {
+        self.mined_tx_id == self.replacement_tx_id
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -311,6 +1364,32 @@ pub struct SignatureResponse {
     pub v: u64,
 }
 
+impl SignatureResponse {
+    /// Assemble the signature, normalizing `v` from either 0/1 or 27/28
+    pub fn to_signature(&self) -> Result<PrimitiveSignature, FireblocksError> {
+        let parse_scalar = |value: &str| {
+            U256::from_str_radix(value.trim_start_matches("0x"), 16)
+                .map_err(|e| FireblocksError::InvalidSignatureError(e.to_string()))
+        };
+        let y_parity = match self.v {
+            0 | 27 => false,
+            1 | 28 => true,
+            v => {
+                return Err(FireblocksError::InvalidSignatureError(format!(
+                    "Invalid v value {}",
+                    v
+                )))
+            }
+        };
+
+        Ok(PrimitiveSignature::new(
+            parse_scalar(&self.r)?,
+            parse_scalar(&self.s)?,
+            y_parity,
+        ))
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RawMessageData {
@@ -320,20 +1399,75 @@ pub struct RawMessageData {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UnsignedMessage {
-    pub content: String,
+    /// Hex encoded message for ETH_MESSAGE, typed data object for EIP712
+    pub content: serde_json::Value,
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<RawMessageType>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Options sent as headers with mutating requests
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RequestOptions {
+    /// Sent as `Idempotency-Key`, so Fireblocks deduplicates resent requests
     #[serde(rename = "idempotencyKey")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub idempotency_key: Option<String>,
+    /// Sent as `X-End-User-Wallet-Id` for non-custodial wallet requests
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ncw: Option<NCW>,
+    /// Derive the idempotency key of a created transaction from its external
+    /// tx id when no key is set
+    #[serde(skip)]
+    pub derive_idempotency_key: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl RequestOptions {
+    /// Create options with an idempotency key derived from an operation and
+    /// an external id, so the same logical request always carries the same key
+    pub fn from_external_id(operation: &str, external_id: &str) -> Self {
+        Self::default().with_idempotency_key(idempotency_key_for(operation, external_id))
+    }
+
+    /// Builder pattern for idempotency key
+    pub fn with_idempotency_key(mut self, idempotency_key: String) -> Self {
+        self.idempotency_key = Some(idempotency_key);
+        self
+    }
+
+    /// Builder pattern for deriving the idempotency key
+    pub fn with_derived_idempotency_key(mut self, derive_idempotency_key: bool) -> Self {
+        self.derive_idempotency_key = derive_idempotency_key;
+        self
+    }
+
+    /// Builder pattern for NCW wallet id
+    pub fn with_ncw_wallet_id(mut self, wallet_id: String) -> Self {
+        self.ncw = Some(NCW {
+            wallet_id: Some(wallet_id),
+        });
+        self
+    }
+
+    /// Get the NCW wallet id, if any
+    pub fn ncw_wallet_id(&self) -> Option<&str> {
+        self.ncw.as_ref()?.wallet_id.as_deref()
+    }
+}
+
+/// Derive a UUID formatted idempotency key from the SHA-256 of an operation,
+/// e.g. `POST /v1/transactions`, and an external id, so the same id used by
+/// different operations gives different keys
+pub fn idempotency_key_for(operation: &str, external_id: &str) -> String {
+    let digest =
+        Sha256::digest(format!("alloy-fireblocks:{}:{}", operation, external_id).as_bytes());
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    Uuid::from_bytes(bytes).to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NCW {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub wallet_id: Option<String>,
@@ -402,6 +1536,74 @@ pub enum ChainId {
     LACHAIN = 274,
 }
 
+impl ChainId {
+    /// Get the chain with the given numeric id, if it is supported
+    pub fn from_id(id: u64) -> Option<Self> {
+        match id {
+            1 => Some(ChainId::MAINNET),
+            3 => Some(ChainId::ROPSTEN),
+            42 => Some(ChainId::KOVAN),
+            5 => Some(ChainId::GOERLI),
+            4 => Some(ChainId::RINKEBY),
+            11155111 => Some(ChainId::SEPOLIA),
+            17000 => Some(ChainId::HOLESKY),
+            56 => Some(ChainId::BSC),
+            97 => Some(ChainId::BSC_TEST),
+            137 => Some(ChainId::POLYGON),
+            80001 => Some(ChainId::POLYGON_TEST),
+            80002 => Some(ChainId::POLYGON_AMOY),
+            43114 => Some(ChainId::AVALANCHE),
+            43113 => Some(ChainId::AVALANCHE_TEST),
+            1285 => Some(ChainId::MOONRIVER),
+            1284 => Some(ChainId::MOONBEAM),
+            19 => Some(ChainId::SONGBIRD),
+            42161 => Some(ChainId::ARBITRUM),
+            421614 => Some(ChainId::ARBITRUM_SEPOLIA),
+            421611 => Some(ChainId::ARBITRUM_RIN),
+            250 => Some(ChainId::FANTOM),
+            30 => Some(ChainId::RSK),
+            31 => Some(ChainId::RSK_TEST),
+            42220 => Some(ChainId::CELO),
+            62320 => Some(ChainId::CELO_BAK),
+            44787 => Some(ChainId::CELO_ALF),
+            10 => Some(ChainId::OPTIMISM),
+            11155420 => Some(ChainId::OPTIMISM_SEPOLIA),
+            69 => Some(ChainId::OPTIMISM_KOVAN),
+            2020 => Some(ChainId::RONIN),
+            7700 => Some(ChainId::CANTO),
+            7701 => Some(ChainId::CANTO_TEST),
+            1442 => Some(ChainId::POLYGON_ZKEVM_TEST),
+            1101 => Some(ChainId::POLYGON_ZKEVM),
+            2222 => Some(ChainId::KAVA),
+            10000 => Some(ChainId::SMARTBCH),
+            10001 => Some(ChainId::SMARTBCH_TEST),
+            128 => Some(ChainId::HECO),
+            1313161554 => Some(ChainId::AURORA),
+            7777 => Some(ChainId::RISEOFTHEWARBOTSTESTNET),
+            9001 => Some(ChainId::EVMOS),
+            592 => Some(ChainId::ASTAR),
+            106 => Some(ChainId::VELAS),
+            421613 => Some(ChainId::ARB_GOERLI),
+            50 => Some(ChainId::XDC),
+            8453 => Some(ChainId::BASE),
+            84532 => Some(ChainId::BASE_SEPOLIA),
+            88888 => Some(ChainId::IVAR),
+            81 => Some(ChainId::JOC),
+            248 => Some(ChainId::OASYS),
+            148 => Some(ChainId::SHIMMEREVM),
+            59144 => Some(ChainId::LINEA),
+            59140 => Some(ChainId::LINEA_TEST),
+            14 => Some(ChainId::FLARE),
+            5000 => Some(ChainId::MANTLE),
+            5001 => Some(ChainId::MANTLE_TEST),
+            81457 => Some(ChainId::BLAST),
+            1946 => Some(ChainId::SONEIUM_MINATO),
+            274 => Some(ChainId::LACHAIN),
+            _ => None,
+        }
+    }
+}
+
 /// Fireblocks Base Api Url
 /// Documentation https://developers.fireblocks.com/reference/signing-a-request-jwt-structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -410,6 +1612,8 @@ pub enum ApiBaseUrl {
     Sandbox,
     EU,
     EU2,
+    /// Custom base url, e.g. a proxy or a local mock server
+    Custom(String),
 }
 
 impl ApiBaseUrl {
@@ -420,6 +1624,16 @@ impl ApiBaseUrl {
             ApiBaseUrl::Sandbox => "https://sandbox-api.fireblocks.io",
             ApiBaseUrl::EU => "https://eu-api.fireblocks.io/v1",
             ApiBaseUrl::EU2 => "https://eu2-api.fireblocks.io/v1",
+            ApiBaseUrl::Custom(url) => url.as_str(),
+        }
+    }
+
+    /// Returns the URL of the Fireblocks console for the workspace. Custom
+    /// base urls are assumed to front a production workspace
+    pub fn console_url(&self) -> &str {
+        match self {
+            ApiBaseUrl::Sandbox => "https://sandbox.fireblocks.io",
+            _ => "https://console.fireblocks.io",
         }
     }
 }
@@ -716,15 +1930,24 @@ impl FeeLevel {
     }
 }
 
+/// Default deadline for waiting on a transaction, in milliseconds
+pub const DEFAULT_POLLING_TIMEOUT: u64 = 600_000;
+
+/// Default minimum time between reloads of the address book, in milliseconds
+pub const DEFAULT_ADDRESS_BOOK_REFRESH_INTERVAL: u64 = 60_000;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FireblocksProviderConfig {
     // Mandatory fields
-    // TODO: use zeroize/secrecy
-    /// API Key provided by Fireblocks
-    pub api_key: String,
-    // TODO: use zeroize/secrecy
-    /// Private key provided by Fireblocks
-    pub private_key: String,
+    /// API Key provided by Fireblocks, left out when serializing
+    #[serde(skip_serializing)]
+    pub api_key: SecretString,
+    /// Private key provided by Fireblocks, left out when serializing. Unset
+    /// when requests are signed by an external [`RequestSigner`]
+    ///
+    /// [`RequestSigner`]: crate::request_signer::RequestSigner
+    #[serde(skip_serializing, default)]
+    pub private_key: Option<SecretString>,
     /// ApiBaseUrl enum
     pub api_base_url: ApiBaseUrl,
     /// Chain Id
@@ -746,6 +1969,10 @@ pub struct FireblocksProviderConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub polling_interval: Option<u64>,
 
+    /// Deadline for waiting on a transaction, `None` waits indefinitely
+    #[serde(default = "default_polling_timeout")]
+    pub polling_timeout: Option<u64>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub one_time_addresses_enabled: Option<bool>,
 
@@ -772,6 +1999,21 @@ pub struct FireblocksProviderConfig {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub proxy_path: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connect_timeout: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_timeout: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_request_attempts: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limits: Option<RateLimits>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address_book_refresh_interval: Option<u64>,
 }
 
 impl FireblocksProviderConfig {
@@ -780,12 +2022,27 @@ impl FireblocksProviderConfig {
         private_key: String,
         api_base_url: ApiBaseUrl,
         chain_id: ChainId,
+    ) -> Self {
+        Self {
+            private_key: Some(private_key.into()),
+            ..Self::without_private_key(api_key, api_base_url, chain_id)
+        }
+    }
+
+    /// Create a config without a private key, for a provider signing requests
+    /// with [`FireblocksProvider::from_signer`]
+    ///
+    /// [`FireblocksProvider::from_signer`]: crate::provider::FireblocksProvider::from_signer
+    pub fn without_private_key(
+        api_key: String,
+        api_base_url: ApiBaseUrl,
+        chain_id: ChainId,
     ) -> Self {
         let asset = Asset::get_by_chain_id(chain_id.clone());
         Self {
             // Required fields
-            api_key,
-            private_key,
+            api_key: api_key.into(),
+            private_key: None,
             api_base_url,
             chain_id,
             // Optional fields with defaults
@@ -794,6 +2051,7 @@ impl FireblocksProviderConfig {
             fallback_fee_level: Some(FeeLevel::MEDIUM),
             note: Some("alloy-fireblocks provider".into()),
             polling_interval: Some(1000),
+            polling_timeout: Some(DEFAULT_POLLING_TIMEOUT),
             one_time_addresses_enabled: Some(true),
             external_tx_id: None,
             user_agent: None,
@@ -803,6 +2061,11 @@ impl FireblocksProviderConfig {
             enhanced_error_handling: Some(true),
             gasless_gas_tank_vault_id: None,
             proxy_path: None,
+            connect_timeout: None,
+            request_timeout: None,
+            max_request_attempts: None,
+            rate_limits: None,
+            address_book_refresh_interval: Some(DEFAULT_ADDRESS_BOOK_REFRESH_INTERVAL),
         }
     }
 
@@ -845,6 +2108,26 @@ impl FireblocksProviderConfig {
         self
     }
 
+    /// Builder pattern for polling timeout, in milliseconds
+    pub fn with_polling_timeout(mut self, polling_timeout: u64) -> Self {
+        self.polling_timeout = Some(polling_timeout);
+        self
+    }
+
+    /// Builder pattern for waiting on transactions without a deadline
+    pub fn without_polling_timeout(mut self) -> Self {
+        self.polling_timeout = None;
+        self
+    }
+
+    /// Get the polling options for waiting on transactions
+    pub fn polling_options(&self) -> PollingOptions {
+        PollingOptions::default()
+            .with_interval(Duration::from_millis(self.polling_interval.unwrap_or(1000)))
+            .with_timeout(self.polling_timeout.map(Duration::from_millis))
+            .with_log_status_changes(self.log_transaction_status_changes.unwrap_or(false))
+    }
+
     /// Builder pattern for one time addresses enabled
     pub fn with_one_time_addresses_enabled(mut self, one_time_addresses_enabled: bool) -> Self {
         self.one_time_addresses_enabled = Some(one_time_addresses_enabled);
@@ -902,6 +2185,85 @@ impl FireblocksProviderConfig {
         self.proxy_path = Some(proxy_path);
         self
     }
+
+    /// Builder pattern for connect timeout to the Fireblocks API, in milliseconds
+    pub fn with_connect_timeout(mut self, connect_timeout: u64) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Builder pattern for request timeout to the Fireblocks API, in milliseconds
+    pub fn with_request_timeout(mut self, request_timeout: u64) -> Self {
+        self.request_timeout = Some(request_timeout);
+        self
+    }
+
+    /// Builder pattern for max attempts of each Fireblocks API request, 1 disables retries
+    pub fn with_max_request_attempts(mut self, max_request_attempts: u32) -> Self {
+        self.max_request_attempts = Some(max_request_attempts);
+        self
+    }
+
+    /// Builder pattern for client side rate limits of Fireblocks API requests
+    pub fn with_rate_limits(mut self, rate_limits: RateLimits) -> Self {
+        self.rate_limits = Some(rate_limits);
+        self
+    }
+
+    /// Builder pattern for the minimum time between reloads of the address
+    /// book on unknown destinations, in milliseconds
+    pub fn with_address_book_refresh_interval(
+        mut self,
+        address_book_refresh_interval: u64,
+    ) -> Self {
+        self.address_book_refresh_interval = Some(address_book_refresh_interval);
+        self
+    }
+
+    /// Wrap the config so it serializes with its credentials, e.g. to write
+    /// a config file. Serializing the config itself leaves them out
+    pub fn expose_credentials(&self) -> ExposedCredentials<'_> {
+        ExposedCredentials {
+            api_key: &self.api_key,
+            private_key: self.private_key.as_ref(),
+            config: self,
+        }
+    }
+}
+
+/// A [`FireblocksProviderConfig`] serialized with its credentials
+#[derive(Serialize)]
+pub struct ExposedCredentials<'a> {
+    #[serde(serialize_with = "serialize_secret")]
+    api_key: &'a SecretString,
+    #[serde(
+        serialize_with = "serialize_optional_secret",
+        skip_serializing_if = "Option::is_none"
+    )]
+    private_key: Option<&'a SecretString>,
+    #[serde(flatten)]
+    config: &'a FireblocksProviderConfig,
+}
+
+fn default_polling_timeout() -> Option<u64> {
+    Some(DEFAULT_POLLING_TIMEOUT)
+}
+
+fn serialize_secret<S: Serializer>(
+    secret: &&SecretString,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(secret.expose_secret())
+}
+
+fn serialize_optional_secret<S: Serializer>(
+    secret: &Option<&SecretString>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match secret {
+        Some(secret) => serializer.serialize_str(secret.expose_secret()),
+        None => serializer.serialize_none(),
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -919,14 +2281,181 @@ pub struct ProviderRpcError {
     pub payload: RequestArguments<serde_json::Value>,
 }
 
+impl ProviderRpcError {
+    /// The user rejected the request
+    pub const USER_REJECTED_REQUEST: i32 = 4001;
+    /// The requested method and/or account has not been authorized
+    pub const UNAUTHORIZED: i32 = 4100;
+    /// The provider does not support the requested method
+    pub const UNSUPPORTED_METHOD: i32 = 4200;
+    /// The provider is disconnected from all chains
+    pub const DISCONNECTED: i32 = 4900;
+    /// The provider is not connected to the requested chain
+    pub const CHAIN_DISCONNECTED: i32 = 4901;
+    /// Invalid method parameters
+    pub const INVALID_PARAMS: i32 = -32602;
+    /// Internal JSON-RPC error
+    pub const INTERNAL_ERROR: i32 = -32603;
+    /// Missing or invalid input, e.g. insufficient funds
+    pub const INVALID_INPUT: i32 = -32000;
+    /// The transaction was rejected, e.g. by a policy
+    pub const TRANSACTION_REJECTED: i32 = -32003;
+    /// Request exceeds a defined limit
+    pub const LIMIT_EXCEEDED: i32 = -32005;
+
+    pub fn new(
+        code: i32,
+        message: impl Into<String>,
+        payload: RequestArguments<serde_json::Value>,
+    ) -> Self {
+        Self {
+            message: message.into(),
+            code,
+            data: None,
+            payload,
+        }
+    }
+
+    /// Builder pattern for data
+    pub fn with_data(mut self, data: String) -> Self {
+        self.data = Some(data);
+        self
+    }
+}
+
 impl std::error::Error for ProviderRpcError {}
 
+impl From<ProviderRpcError> for ErrorPayload {
+    fn from(error: ProviderRpcError) -> Self {
+        // Data that is not JSON is passed on as a JSON string
+        let data = error.data.map(|data| {
+            RawValue::from_string(data.clone())
+                .or_else(|_| serde_json::value::to_raw_value(&data))
+                .expect("a JSON string is valid JSON")
+        });
+        ErrorPayload {
+            code: error.code as i64,
+            message: error.message.into(),
+            data,
+        }
+    }
+}
+
 impl std::fmt::Display for ProviderRpcError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "RPC Error {}: {}", self.code, self.message)
     }
 }
 
+/// An unsuccessful response from the Fireblocks API
+#[derive(Debug, Clone, Error)]
+#[error("{message} (status {status}, code {code:?}, request id {request_id:?})")]
+pub struct FireblocksApiError {
+    /// HTTP status of the response
+    pub status: StatusCode,
+    /// Fireblocks error code, if the body carried one
+    pub code: Option<i64>,
+    /// Error message from the body, or the status reason without one
+    pub message: String,
+    /// `X-Request-ID` header, to quote when contacting Fireblocks support
+    pub request_id: Option<String>,
+    /// Category of the error
+    pub kind: ApiErrorKind,
+}
+
+/// Error body returned by the Fireblocks API
+#[derive(Debug, Deserialize)]
+struct ApiErrorBody {
+    message: Option<String>,
+    code: Option<i64>,
+}
+
+impl FireblocksApiError {
+    /// Decode an unsuccessful response from its status, body and request id
+    pub fn from_response(status: StatusCode, body: &str, request_id: Option<String>) -> Self {
+        let decoded = serde_json::from_str::<ApiErrorBody>(body).ok();
+        let code = decoded.as_ref().and_then(|body| body.code);
+        let message = decoded
+            .and_then(|body| body.message)
+            .or_else(|| Some(body.trim().to_string()).filter(|body| !body.is_empty()))
+            .unwrap_or_else(|| status.canonical_reason().unwrap_or("Unknown").to_string());
+
+        Self {
+            status,
+            code,
+            kind: ApiErrorKind::of(code, &message),
+            message,
+            request_id,
+        }
+    }
+}
+
+/// Well known categories of Fireblocks API errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiErrorKind {
+    /// The source does not hold enough funds for the amount and fees
+    InsufficientBalance,
+    /// The vault account does not exist or the id is invalid
+    UnknownVault,
+    /// The destination is not whitelisted for the workspace
+    WhitelistViolation,
+    /// The address is not valid for the asset
+    InvalidAddress,
+    /// The Transaction Authorization Policy rejected the request
+    PolicyRejection,
+    /// Any other error
+    Other,
+}
+
+impl ApiErrorKind {
+    /// Classify an error by its Fireblocks code, falling back to its message
+    /// when the code is missing or not a well known one
+    pub fn of(code: Option<i64>, message: &str) -> Self {
+        code.and_then(Self::from_code)
+            .unwrap_or_else(|| Self::classify(message))
+    }
+
+    /// Get the category of a well known Fireblocks error code
+    pub fn from_code(code: i64) -> Option<Self> {
+        match code {
+            1427 | 1428 => Some(ApiErrorKind::InsufficientBalance),
+            11001..=11003 => Some(ApiErrorKind::UnknownVault),
+            1409 | 1410 => Some(ApiErrorKind::WhitelistViolation),
+            1404 | 1433 => Some(ApiErrorKind::InvalidAddress),
+            1450 | 1451 => Some(ApiErrorKind::PolicyRejection),
+            _ => None,
+        }
+    }
+
+    /// Classify an error from its message, for responses without a well
+    /// known code
+    pub fn classify(message: &str) -> Self {
+        let message = message.to_lowercase();
+        let mentions_any = |words: &[&str]| words.iter().any(|word| message.contains(word));
+
+        if mentions_any(&["insufficient funds", "insufficient balance"]) {
+            ApiErrorKind::InsufficientBalance
+        } else if mentions_any(&["not whitelisted", "whitelist violation"]) {
+            ApiErrorKind::WhitelistViolation
+        } else if mentions_any(&[
+            "transaction authorization policy",
+            "tap rule",
+            "blocked by policy",
+            "rejected by policy",
+        ]) {
+            ApiErrorKind::PolicyRejection
+        } else if message.contains("vault")
+            && mentions_any(&["not found", "does not exist", "invalid", "unknown"])
+        {
+            ApiErrorKind::UnknownVault
+        } else if message.contains("address") && mentions_any(&["invalid", "not valid"]) {
+            ApiErrorKind::InvalidAddress
+        } else {
+            ApiErrorKind::Other
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum FireblocksError {
     #[error("Invalid account ID format '{0}': {1}")]
@@ -953,6 +2482,12 @@ pub enum FireblocksError {
     #[error("Sign JWT Error: {0}")]
     SignJWTError(String),
 
+    #[error("Private Key Error: {0}")]
+    PrivateKeyError(String),
+
+    #[error("Invalid config {0}: {1}")]
+    ConfigError(String, String),
+
     #[error("Header Error: {0}")]
     HeaderError(String),
 
@@ -962,6 +2497,57 @@ pub enum FireblocksError {
     #[error("Failed to populate accounts")]
     FailedToPopulateAccountsError(),
 
+    #[error("Transaction is missing a from address")]
+    MissingFromAddressError(),
+
+    #[error("Transaction is missing a to address")]
+    MissingToAddressError(),
+
+    #[error("Address {0} is not a known vault account")]
+    UnknownAccountError(String),
+
+    #[error("Chain ID mismatch: expected {0}, got {1}")]
+    ChainIdMismatchError(u64, u64),
+
+    #[error("Transaction {0} failed with status {1:?}: {2}")]
+    TransactionFailedError(String, TransactionStatus, String),
+
+    #[error("Timed out waiting for transaction {0}, last status {1:?}")]
+    TransactionTimeoutError(String, TransactionStatus),
+
+    #[error("No vault or whitelisted wallet found for {0} and one time addresses are disabled")]
+    UnresolvedDestinationError(String),
+
+    #[error("Invalid transaction arguments: {0}")]
+    InvalidTransactionArgumentsError(String),
+
+    #[error("Transaction {0} cannot be replaced in status {1:?}")]
+    TransactionNotReplaceableError(String, TransactionStatus),
+
+    #[error("Transaction {0} completed without a transaction hash")]
+    MissingTxHashError(String),
+
+    #[error("Transaction {0} completed without a signature")]
+    MissingSignatureError(String),
+
+    #[error("Invalid signature: {0}")]
+    InvalidSignatureError(String),
+
+    #[error("Signature does not match address {0}")]
+    SignatureMismatchError(String),
+
+    #[error("Invalid typed data: {0}")]
+    TypedDataError(String),
+
+    #[error("Fireblocks API Error: {0}")]
+    ApiError(FireblocksApiError),
+
+    #[error("HTTP Client Error: {0}")]
+    HttpClientError(String),
+
+    #[error("Listing stopped after {0} items, raise the pager's max items to list more")]
+    PaginationLimitError(usize),
+
     #[error("Transport Error: {0}")]
     TransportError(String),
 
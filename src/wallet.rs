@@ -0,0 +1,134 @@
+use std::collections::BTreeMap;
+
+use alloy_consensus::{SignableTransaction, TxEnvelope, TypedTransaction};
+use alloy_core::primitives::{Address, PrimitiveSignature};
+use alloy_network::{Ethereum, NetworkWallet, TxSigner};
+use alloy_signer::{Error, Result, Signer};
+
+use crate::{signer::FireblocksSigner, types::FireblocksError};
+
+/// A wallet holding a [`FireblocksSigner`] per vault account.
+///
+/// Transactions are signed by the vault owning the `from` address, so a single
+/// wallet can be handed to `ProviderBuilder::wallet` for multi-vault setups.
+#[derive(Debug, Clone)]
+pub struct FireblocksWallet {
+    /// Address used when a transaction does not specify a sender
+    default: Address,
+    /// Signers keyed by vault account id
+    signers: BTreeMap<u64, FireblocksSigner>,
+}
+
+impl From<FireblocksSigner> for FireblocksWallet {
+    fn from(signer: FireblocksSigner) -> Self {
+        Self::new(signer)
+    }
+}
+
+impl FireblocksWallet {
+    /// Create a new wallet with the given signer as the default signer
+    pub fn new(signer: FireblocksSigner) -> Self {
+        let mut wallet = Self {
+            default: Signer::address(&signer),
+            signers: BTreeMap::new(),
+        };
+        wallet.register_signer(signer);
+        wallet
+    }
+
+    /// Register a signer for its vault account, replacing any previous signer
+    /// for the same vault
+    pub fn register_signer(&mut self, signer: FireblocksSigner) {
+        self.signers.insert(signer.vault_id(), signer);
+    }
+
+    /// Builder pattern for additional signers
+    pub fn with_signer(mut self, signer: FireblocksSigner) -> Self {
+        self.register_signer(signer);
+        self
+    }
+
+    /// Builder pattern for the default signer, which must already be registered
+    pub fn with_default_signer(mut self, address: Address) -> Result<Self, FireblocksError> {
+        if self.signer_by_address(&address).is_none() {
+            return Err(FireblocksError::UnknownAccountError(address.to_string()));
+        }
+        self.default = address;
+        Ok(self)
+    }
+
+    /// Get the vault id to address map of all registered signers
+    pub fn accounts(&self) -> BTreeMap<u64, Address> {
+        self.signers
+            .iter()
+            .map(|(vault_id, signer)| (*vault_id, Signer::address(signer)))
+            .collect()
+    }
+
+    /// Get the signer for a vault account
+    pub fn signer_by_vault_id(&self, vault_id: u64) -> Option<&FireblocksSigner> {
+        self.signers.get(&vault_id)
+    }
+
+    /// Get the signer for an address
+    pub fn signer_by_address(&self, address: &Address) -> Option<&FireblocksSigner> {
+        self.signers
+            .values()
+            .find(|signer| Signer::address(*signer) == *address)
+    }
+
+    /// Sign a transaction with the vault owning `sender`
+    async fn sign_transaction_inner(
+        &self,
+        sender: Address,
+        tx: &mut dyn SignableTransaction<PrimitiveSignature>,
+    ) -> Result<PrimitiveSignature> {
+        self.signer_by_address(&sender)
+            .ok_or_else(|| Error::other(FireblocksError::UnknownAccountError(sender.to_string())))?
+            .sign_transaction(tx)
+            .await
+    }
+}
+
+impl NetworkWallet<Ethereum> for FireblocksWallet {
+    fn default_signer_address(&self) -> Address {
+        self.default
+    }
+
+    fn has_signer_for(&self, address: &Address) -> bool {
+        self.signer_by_address(address).is_some()
+    }
+
+    fn signer_addresses(&self) -> impl Iterator<Item = Address> {
+        self.signers.values().map(Signer::address)
+    }
+
+    async fn sign_transaction_from(
+        &self,
+        sender: Address,
+        tx: TypedTransaction,
+    ) -> Result<TxEnvelope> {
+        match tx {
+            TypedTransaction::Legacy(mut t) => {
+                let signature = self.sign_transaction_inner(sender, &mut t).await?;
+                Ok(t.into_signed(signature).into())
+            }
+            TypedTransaction::Eip2930(mut t) => {
+                let signature = self.sign_transaction_inner(sender, &mut t).await?;
+                Ok(t.into_signed(signature).into())
+            }
+            TypedTransaction::Eip1559(mut t) => {
+                let signature = self.sign_transaction_inner(sender, &mut t).await?;
+                Ok(t.into_signed(signature).into())
+            }
+            TypedTransaction::Eip4844(mut t) => {
+                let signature = self.sign_transaction_inner(sender, &mut t).await?;
+                Ok(t.into_signed(signature).into())
+            }
+            TypedTransaction::Eip7702(mut t) => {
+                let signature = self.sign_transaction_inner(sender, &mut t).await?;
+                Ok(t.into_signed(signature).into())
+            }
+        }
+    }
+}
@@ -0,0 +1,431 @@
+mod common;
+
+use std::{
+    collections::HashSet,
+    time::{Duration, Instant},
+};
+
+use reqwest::StatusCode;
+use serde_json::json;
+use tokio::fs;
+use wiremock::{
+    matchers::{header, method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+use alloy_fireblocks::{
+    api::{FireblocksClient, FireblocksClientBuilder},
+    provider::FireblocksProvider,
+    types::{
+        ApiBaseUrl, ApiErrorKind, FireblocksApiError, FireblocksError, RequestOptions, RetryPolicy,
+    },
+};
+use common::*;
+
+// Helper to create a client builder pointing at the given API URL
+async fn client_builder(api_url: String) -> FireblocksClientBuilder {
+    let private_key = fs::read_to_string("tests/fixtures/test_private_key.pem")
+        .await
+        .unwrap();
+
+    FireblocksClient::builder(
+        private_key,
+        "test-api-key".to_string(),
+        ApiBaseUrl::Custom(api_url),
+    )
+}
+
+// Helper to mock an empty list of internal wallets
+async fn mock_internal_wallets(server: &MockServer, delay: Duration) {
+    Mock::given(method("GET"))
+        .and(path("/v1/internal_wallets"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!([]))
+                .set_delay(delay),
+        )
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_client_builder_user_agent() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/internal_wallets"))
+        .and(header("user-agent", "batch-job/1.0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = client_builder(server.uri())
+        .await
+        .with_user_agent("batch-job/1.0".to_string())
+        .with_pool_max_idle_per_host(4)
+        .with_pool_idle_timeout(Duration::from_secs(30))
+        .build()
+        .unwrap();
+
+    // Clones share the configured HTTP client
+    client.get_internal_wallets().await.unwrap();
+    client.clone().get_internal_wallets().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_client_builder_timeout() {
+    let server = MockServer::start().await;
+    mock_internal_wallets(&server, Duration::from_millis(500)).await;
+
+    let client = client_builder(server.uri())
+        .await
+        .with_connect_timeout(Duration::from_secs(1))
+        .with_timeout(Duration::from_millis(50))
+        .build()
+        .unwrap();
+
+    match client.get_internal_wallets().await {
+        Err(FireblocksError::SendError(_)) => {}
+        other => panic!("Expected a timeout, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_client_builder_proxy() {
+    let proxy = MockServer::start().await;
+    mock_internal_wallets(&proxy, Duration::ZERO).await;
+
+    // The API host does not resolve, so the request only succeeds through the proxy
+    let client = client_builder("http://fireblocks.invalid".to_string())
+        .await
+        .with_proxy(proxy.uri())
+        .build()
+        .unwrap();
+
+    client.get_internal_wallets().await.unwrap();
+    assert_eq!(proxy.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_client_builder_invalid_certificate() {
+    let result = client_builder("http://localhost".to_string())
+        .await
+        .with_root_certificate(b"not a certificate".to_vec())
+        .build();
+
+    assert!(matches!(result, Err(FireblocksError::HttpClientError(_))));
+}
+
+#[tokio::test]
+async fn test_provider_sends_user_agent() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/vault/accounts/0/ETH_TEST5/addresses"))
+        .and(header(
+            "user-agent",
+            format!("alloy-fireblocks/{} treasury", env!("CARGO_PKG_VERSION")).as_str(),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "assetId": "ETH_TEST5",
+            "address": VAULT_ADDRESS.to_string(),
+            "type": "Permanent"
+        }])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = mock_config(&server)
+        .await
+        .with_user_agent("treasury".to_string())
+        .with_request_timeout(5_000);
+    FireblocksProvider::new(config).await.unwrap();
+}
+
+// Helper to create a client retrying quickly, without jitter
+async fn retrying_client(server: &MockServer, max_attempts: u32) -> FireblocksClient {
+    client_builder(server.uri())
+        .await
+        .with_retry_policy(
+            RetryPolicy::default()
+                .with_max_attempts(max_attempts)
+                .with_initial_backoff(Duration::from_millis(1))
+                .with_jitter(false),
+        )
+        .build()
+        .unwrap()
+}
+
+// Helper to mock a failing response for the first `times` matching requests
+async fn mock_failures(server: &MockServer, http_method: &str, status: u16, times: u64) {
+    Mock::given(method(http_method))
+        .respond_with(ResponseTemplate::new(status))
+        .up_to_n_times(times)
+        .with_priority(1)
+        .mount(server)
+        .await;
+}
+
+#[test]
+fn test_retry_policy_backoff() {
+    let policy = RetryPolicy::default()
+        .with_initial_backoff(Duration::from_millis(100))
+        .with_max_backoff(Duration::from_millis(300))
+        .with_jitter(false);
+    assert_eq!(policy.backoff(1), Duration::from_millis(100));
+    assert_eq!(policy.backoff(2), Duration::from_millis(200));
+    assert_eq!(policy.backoff(3), Duration::from_millis(300));
+    assert_eq!(policy.backoff(40), Duration::from_millis(300));
+
+    let policy = policy.with_jitter(true);
+    for _ in 0..20 {
+        let backoff = policy.backoff(2);
+        assert!(backoff >= Duration::from_millis(100) && backoff <= Duration::from_millis(200));
+    }
+}
+
+#[tokio::test]
+async fn test_get_retried_with_fresh_jwt() {
+    let server = MockServer::start().await;
+    let client = retrying_client(&server, 3).await;
+    mock_failures(&server, "GET", 503, 2).await;
+    mock_internal_wallets(&server, Duration::ZERO).await;
+
+    client.get_internal_wallets().await.unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 3);
+    let tokens: HashSet<_> = requests
+        .iter()
+        .map(|req| req.headers.get("authorization").unwrap().clone())
+        .collect();
+    assert_eq!(tokens.len(), 3);
+}
+
+#[tokio::test]
+async fn test_get_retry_attempts_exhausted() {
+    let server = MockServer::start().await;
+    let client = retrying_client(&server, 2).await;
+    mock_failures(&server, "GET", 500, 5).await;
+
+    match client.get_internal_wallets().await {
+        Err(FireblocksError::ApiError(api_error)) => assert_eq!(api_error.status, 500),
+        other => panic!("Expected an API error, got {:?}", other),
+    }
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_client_errors_not_retried() {
+    let server = MockServer::start().await;
+    let client = retrying_client(&server, 3).await;
+    mock_failures(&server, "GET", 400, 5).await;
+
+    assert!(client.get_internal_wallets().await.is_err());
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_retry_after_honoured() {
+    let server = MockServer::start().await;
+    let client = retrying_client(&server, 2).await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    mock_internal_wallets(&server, Duration::ZERO).await;
+
+    let start = Instant::now();
+    client.get_internal_wallets().await.unwrap();
+    assert!(start.elapsed() >= Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn test_retry_after_capped_by_max_backoff() {
+    let server = MockServer::start().await;
+    let client = client_builder(server.uri())
+        .await
+        .with_retry_policy(
+            RetryPolicy::default()
+                .with_max_attempts(2)
+                .with_max_backoff(Duration::from_millis(50)),
+        )
+        .build()
+        .unwrap();
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "3600"))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    mock_internal_wallets(&server, Duration::ZERO).await;
+
+    let start = Instant::now();
+    client.get_internal_wallets().await.unwrap();
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn test_post_not_retried_without_idempotency_key() {
+    let server = MockServer::start().await;
+    let client = retrying_client(&server, 3).await;
+    mock_failures(&server, "POST", 503, 5).await;
+
+    match client.post_request("/v1/vault/accounts", "{}").await {
+        Err(FireblocksError::ApiError(api_error)) => assert_eq!(api_error.status, 503),
+        other => panic!("Expected an API error, got {:?}", other),
+    }
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_post_retried_with_idempotency_key() {
+    let server = MockServer::start().await;
+    let client = retrying_client(&server, 3).await;
+    mock_failures(&server, "POST", 503, 1).await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/vault/accounts"))
+        .and(header("idempotency-key", "create-vault-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client
+        .post_request_with_options(
+            "/v1/vault/accounts",
+            "{}",
+            &RequestOptions::default().with_idempotency_key("create-vault-1".to_string()),
+        )
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 2);
+    assert!(requests
+        .iter()
+        .all(|req| req.headers.get("idempotency-key").unwrap() == "create-vault-1"));
+}
+
+#[tokio::test]
+async fn test_api_error_decoded() {
+    let server = MockServer::start().await;
+    let client = retrying_client(&server, 1).await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/transactions"))
+        .respond_with(
+            ResponseTemplate::new(400)
+                .insert_header("X-Request-ID", "request-1")
+                .set_body_json(json!({
+                    "message": "Insufficient funds to transfer 1 ETH_TEST5",
+                    "code": 1427
+                })),
+        )
+        .mount(&server)
+        .await;
+
+    match client.post_request("/v1/transactions", "{}").await {
+        Err(FireblocksError::ApiError(api_error)) => {
+            assert_eq!(api_error.status, 400);
+            assert_eq!(api_error.code, Some(1427));
+            assert_eq!(
+                api_error.message,
+                "Insufficient funds to transfer 1 ETH_TEST5"
+            );
+            assert_eq!(api_error.request_id.as_deref(), Some("request-1"));
+            assert_eq!(api_error.kind, ApiErrorKind::InsufficientBalance);
+        }
+        other => panic!("Expected an API error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_api_error_without_json_body() {
+    let server = MockServer::start().await;
+    let client = retrying_client(&server, 1).await;
+    mock_failures(&server, "GET", 502, 1).await;
+
+    match client.get_internal_wallets().await {
+        Err(FireblocksError::ApiError(api_error)) => {
+            assert_eq!(api_error.code, None);
+            assert_eq!(api_error.message, "Bad Gateway");
+            assert_eq!(api_error.request_id, None);
+            assert_eq!(api_error.kind, ApiErrorKind::Other);
+        }
+        other => panic!("Expected an API error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_api_error_kinds() {
+    let cases = [
+        ("Insufficient balance", ApiErrorKind::InsufficientBalance),
+        ("Insufficient permissions", ApiErrorKind::Other),
+        ("Updated the signing policy", ApiErrorKind::Other),
+        ("Vault account 42 not found", ApiErrorKind::UnknownVault),
+        ("Invalid vault account id", ApiErrorKind::UnknownVault),
+        (
+            "Destination address is not whitelisted",
+            ApiErrorKind::WhitelistViolation,
+        ),
+        (
+            "Invalid address for asset ETH_TEST5",
+            ApiErrorKind::InvalidAddress,
+        ),
+        (
+            "Transaction blocked by the Transaction Authorization Policy",
+            ApiErrorKind::PolicyRejection,
+        ),
+        ("Something went wrong", ApiErrorKind::Other),
+    ];
+    for (message, kind) in cases {
+        assert_eq!(ApiErrorKind::classify(message), kind, "{}", message);
+    }
+
+    let api_error = FireblocksApiError::from_response(
+        StatusCode::NOT_FOUND,
+        r#"{"message":"Vault account not found","code":11001}"#,
+        None,
+    );
+    assert_eq!(api_error.kind, ApiErrorKind::UnknownVault);
+    assert_eq!(api_error.code, Some(11001));
+}
+
+#[test]
+fn test_api_error_kind_prefers_code() {
+    // The code wins when it disagrees with the message
+    let cases = [
+        (
+            r#"{"message":"Insufficient permissions for vault 3","code":11001}"#,
+            ApiErrorKind::UnknownVault,
+        ),
+        (
+            r#"{"message":"Insufficient funds","code":1450}"#,
+            ApiErrorKind::PolicyRejection,
+        ),
+        (
+            r#"{"message":"Policy engine unavailable","code":1427}"#,
+            ApiErrorKind::InsufficientBalance,
+        ),
+        // Unknown codes fall back to the message
+        (
+            r#"{"message":"Insufficient funds","code":99999}"#,
+            ApiErrorKind::InsufficientBalance,
+        ),
+        (
+            r#"{"message":"Insufficient permissions","code":99999}"#,
+            ApiErrorKind::Other,
+        ),
+    ];
+    for (body, kind) in cases {
+        let api_error = FireblocksApiError::from_response(StatusCode::BAD_REQUEST, body, None);
+        assert_eq!(api_error.kind, kind, "{}", body);
+    }
+}
@@ -0,0 +1,158 @@
+//! Shared helpers for tests running against a local mock Fireblocks server
+#![allow(dead_code)]
+
+use alloy_core::primitives::{address, b256, hex, Address, B256};
+use alloy_fireblocks::{
+    api::FireblocksClient,
+    provider::FireblocksProvider,
+    types::{ApiBaseUrl, ChainId, FireblocksProviderConfig},
+};
+use k256::ecdsa::SigningKey;
+use serde_json::json;
+use tokio::fs;
+use wiremock::{
+    matchers::{body_partial_json, method, path},
+    Mock, MockServer, Request, ResponseTemplate,
+};
+
+// Private key 0x...01 and its address stand in for the vault's key
+pub const VAULT_KEY: B256 =
+    b256!("0x0000000000000000000000000000000000000000000000000000000000000001");
+pub const VAULT_ADDRESS: Address = address!("7E5F4552091A69125d5DfCb7b8C2659029395Bdf");
+
+// Private key 0x...02 and its address stand in for a second vault's key
+pub const SECOND_VAULT_KEY: B256 =
+    b256!("0x0000000000000000000000000000000000000000000000000000000000000002");
+pub const SECOND_VAULT_ADDRESS: Address = address!("2B5AD5c4795c026514f8317c7a215E218DcCD6cF");
+
+// Helper to create a configuration pointing at a local mock Fireblocks server
+pub async fn mock_config(server: &MockServer) -> FireblocksProviderConfig {
+    let private_key = fs::read_to_string("tests/fixtures/test_private_key.pem")
+        .await
+        .unwrap();
+
+    FireblocksProviderConfig::new(
+        "test-api-key".to_string(),
+        private_key,
+        ApiBaseUrl::Custom(server.uri()),
+        ChainId::SEPOLIA,
+    )
+    .with_asset_id("ETH_TEST5".to_string(), server.uri())
+    .with_vault_account_ids(vec![0])
+    .with_polling_interval(10)
+}
+
+// Helper to create a provider backed by a local mock Fireblocks server
+pub async fn mock_provider(server: &MockServer) -> FireblocksProvider {
+    Mock::given(method("GET"))
+        .and(path("/v1/vault/accounts/0/ETH_TEST5/addresses"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "assetId": "ETH_TEST5",
+            "address": VAULT_ADDRESS.to_string(),
+            "type": "Permanent"
+        }])))
+        .mount(server)
+        .await;
+
+    mock_empty_address_book(server).await;
+
+    FireblocksProvider::new(mock_config(server).await)
+        .await
+        .unwrap()
+}
+
+// Helper to mock empty whitelists, at a low priority so tests can mount their own
+pub async fn mock_empty_address_book(server: &MockServer) {
+    for wallets_path in [
+        "/v1/internal_wallets",
+        "/v1/external_wallets",
+        "/v1/contracts",
+    ] {
+        Mock::given(method("GET"))
+            .and(path(wallets_path))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .with_priority(10)
+            .mount(server)
+            .await;
+    }
+}
+
+// Helper to mock a JSON-RPC method on the node behind the provider
+pub async fn mock_rpc(server: &MockServer, rpc_method: &str, result: serde_json::Value) {
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(body_partial_json(json!({ "method": rpc_method })))
+        .respond_with(move |req: &Request| {
+            let body: serde_json::Value = serde_json::from_slice(&req.body).unwrap();
+            ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": body["id"],
+                "result": result
+            }))
+        })
+        .mount(server)
+        .await;
+}
+
+// Helper to sign a prehashed message with the vault key, as Fireblocks would
+pub fn vault_signature(hash: B256) -> serde_json::Value {
+    signature_with_key(VAULT_KEY, hash)
+}
+
+// Helper to sign a prehashed message with an arbitrary key, as Fireblocks would
+pub fn signature_with_key(key: B256, hash: B256) -> serde_json::Value {
+    let key = SigningKey::from_slice(key.as_slice()).unwrap();
+    let (signature, recovery_id) = key.sign_prehash_recoverable(hash.as_slice()).unwrap();
+    let (r, s) = signature.split_bytes();
+
+    json!({
+        "fullSig": hex::encode([r.as_slice(), s.as_slice()].concat()),
+        "r": hex::encode(r),
+        "s": hex::encode(s),
+        "v": recovery_id.to_byte()
+    })
+}
+
+// Helper to mock a completed RAW signing transaction
+pub async fn mock_signing_tx(server: &MockServer, tx_id: &str, signature: serde_json::Value) {
+    Mock::given(method("POST"))
+        .and(path("/v1/transactions"))
+        .and(body_partial_json(json!({ "operation": "RAW" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": tx_id,
+            "status": "SUBMITTED"
+        })))
+        .mount(server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/transactions/{}", tx_id)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": tx_id,
+            "assetId": "ETH_TEST5",
+            "status": "COMPLETED",
+            "signedMessages": [{
+                "content": "",
+                "algorithm": "MPC_ECDSA_SECP256K1",
+                "derivationPath": [44, 60, 0, 0, 0],
+                "signature": signature,
+                "publicKey": ""
+            }]
+        })))
+        .mount(server)
+        .await;
+}
+
+// Helper to create a Fireblocks client pointing at a local mock Fireblocks server
+pub async fn mock_client(server: &MockServer) -> FireblocksClient {
+    let private_key = fs::read_to_string("tests/fixtures/test_private_key.pem")
+        .await
+        .unwrap();
+
+    FireblocksClient::new(
+        private_key,
+        "test-api-key".to_string(),
+        ApiBaseUrl::Custom(server.uri()),
+    )
+    .unwrap()
+}
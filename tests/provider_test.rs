@@ -1,13 +1,35 @@
-use std::sync::Arc;
+mod common;
 
-use alloy_core::primitives::Address;
-use alloy_transport::TransportError;
+use std::{sync::Arc, time::Duration};
+
+use alloy_core::{
+    dyn_abi::TypedData,
+    primitives::{
+        address, b256, bytes, eip191_hash_message, hex, Address, PrimitiveSignature, B256, U256,
+    },
+    sol,
+    sol_types::{eip712_domain, SolStruct},
+};
+use alloy_provider::Provider;
+use alloy_rpc_types_eth::TransactionRequest;
+use alloy_transport::{RpcError, TransportError, TransportErrorKind};
 
 use alloy_fireblocks::{
     provider::FireblocksProvider,
-    types::{ApiBaseUrl, ChainId, FireblocksProviderConfig},
+    request_signer::{ExternalSigner, RequestSigner, RsaKeySigner},
+    types::{
+        ApiBaseUrl, ChainId, FeeBump, FeeLevel, FireblocksError, FireblocksProviderConfig,
+        PeerType, ProviderRpcError, RequestArguments, TransactionStatus,
+    },
 };
+use serde_json::json;
 use tokio::fs;
+use wiremock::{
+    matchers::{body_partial_json, method, path},
+    Mock, MockServer, Request, ResponseTemplate,
+};
+
+use common::*;
 
 // Helper to create test configuration
 async fn test_config() -> FireblocksProviderConfig {
@@ -36,6 +58,44 @@ async fn test_provider_creation() -> Result<(), TransportError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_provider_from_signer_without_private_key() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/vault/accounts/0/ETH_TEST5/addresses"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "assetId": "ETH_TEST5",
+            "address": VAULT_ADDRESS.to_string(),
+            "type": "Permanent"
+        }])))
+        .mount(&server)
+        .await;
+
+    let config = FireblocksProviderConfig::without_private_key(
+        "test-api-key".to_string(),
+        ApiBaseUrl::Custom(server.uri()),
+        ChainId::SEPOLIA,
+    )
+    .with_asset_id("ETH_TEST5".to_string(), server.uri())
+    .with_vault_account_ids(vec![0]);
+
+    match FireblocksProvider::new(config.clone()).await {
+        Err(e) => assert!(e.to_string().contains("private_key"), "{}", e),
+        Ok(_) => panic!("Expected the missing private key to be reported"),
+    }
+
+    // Stands in for a KMS holding the key
+    let kms = Arc::new(RsaKeySigner::from_pem_file("tests/fixtures/test_private_key.pem").unwrap());
+    let signer = ExternalSigner::new(move |message| {
+        let kms = kms.clone();
+        async move { kms.sign(&message).await }
+    });
+    let provider = FireblocksProvider::from_signer(config, Arc::new(signer))
+        .await
+        .unwrap();
+    assert_eq!(provider.get_addresses().unwrap(), vec![VAULT_ADDRESS]);
+}
+
 #[tokio::test]
 async fn test_user_agent() {
     let config = test_config().await;
@@ -115,3 +175,1278 @@ async fn test_populate_accounts() {
         println!("Vault ID: {}, Address: {}", vault_id, address);
     }
 }
+
+#[tokio::test]
+async fn test_create_fireblocks_transaction() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+    let tx_hash = b256!("0xa1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90");
+
+    Mock::given(method("POST"))
+        .and(path("/v1/transactions"))
+        .and(body_partial_json(json!({
+            "assetId": "ETH_TEST5",
+            "operation": "CONTRACT_CALL",
+            "source": { "type": "VAULT_ACCOUNT", "id": "0" },
+            "destination": {
+                "type": "ONE_TIME_ADDRESS",
+                "oneTimeAddress": { "address": "0x000000000000000000000000000000000000dEaD" }
+            },
+            "amount": "1.000000000000000000",
+            "extraParameters": { "contractCallData": "0xdeadbeef" },
+            "feeLevel": "MEDIUM",
+            "note": "alloy-fireblocks provider"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "tx-1",
+            "status": "SUBMITTED"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/transactions/tx-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "tx-1",
+            "assetId": "ETH_TEST5",
+            "txHash": tx_hash.to_string(),
+            "status": "COMPLETED",
+            "subStatus": "CONFIRMED"
+        })))
+        .mount(&server)
+        .await;
+
+    let tx = TransactionRequest::default()
+        .from(VAULT_ADDRESS)
+        .to(address!("000000000000000000000000000000000000dEaD"))
+        .value(U256::from(10).pow(U256::from(18)))
+        .input(bytes!("deadbeef").into());
+
+    let hash = provider.create_fireblocks_transaction(&tx).await.unwrap();
+    assert_eq!(hash, tx_hash);
+}
+
+#[tokio::test]
+async fn test_create_fireblocks_transaction_with_fees() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/transactions"))
+        .and(body_partial_json(json!({
+            "operation": "TRANSFER",
+            "gasLimit": "50000",
+            "maxFee": "20",
+            "priorityFee": "1.5"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "tx-fees",
+            "status": "SUBMITTED"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    mock_tx_status(&server, "tx-fees", "COMPLETED", None).await;
+
+    let tx = TransactionRequest::default()
+        .from(VAULT_ADDRESS)
+        .to(address!("000000000000000000000000000000000000dEaD"))
+        .value(U256::from(1))
+        .gas_limit(50_000)
+        .max_fee_per_gas(20_000_000_000)
+        .max_priority_fee_per_gas(1_500_000_000);
+
+    provider.create_fireblocks_transaction(&tx).await.unwrap();
+
+    // Explicit fees replace the fallback fee level
+    let requests = server.received_requests().await.unwrap();
+    let body: serde_json::Value = requests
+        .iter()
+        .find(|req| req.method.as_str() == "POST" && req.url.path() == "/v1/transactions")
+        .map(|req| serde_json::from_slice(&req.body).unwrap())
+        .unwrap();
+    assert!(body.get("feeLevel").is_none());
+}
+
+// Helper to mock a POST creating a transfer to the given destination
+async fn mock_transfer_to(server: &MockServer, tx_id: &str, destination: serde_json::Value) {
+    Mock::given(method("POST"))
+        .and(path("/v1/transactions"))
+        .and(body_partial_json(json!({ "destination": destination })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": tx_id,
+            "status": "SUBMITTED"
+        })))
+        .expect(1)
+        .mount(server)
+        .await;
+    mock_tx_status(server, tx_id, "COMPLETED", None).await;
+}
+
+#[tokio::test]
+async fn test_create_fireblocks_transaction_to_vault() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+
+    mock_transfer_to(
+        &server,
+        "tx-vault",
+        json!({ "type": "VAULT_ACCOUNT", "id": "0" }),
+    )
+    .await;
+
+    let tx = TransactionRequest::default()
+        .from(VAULT_ADDRESS)
+        .to(VAULT_ADDRESS)
+        .value(U256::from(1));
+    provider.create_fireblocks_transaction(&tx).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_create_fireblocks_transaction_defaults_from() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/transactions"))
+        .and(body_partial_json(json!({
+            "source": { "type": "VAULT_ACCOUNT", "id": "0" }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "tx-default",
+            "status": "SUBMITTED"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    mock_tx_status(&server, "tx-default", "COMPLETED", None).await;
+
+    // As sent by a bare contract call builder
+    let tx = TransactionRequest::default()
+        .to(address!("000000000000000000000000000000000000dEaD"))
+        .input(bytes!("deadbeef").into());
+    provider.create_fireblocks_transaction(&tx).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_create_fireblocks_transaction_to_whitelisted_wallet() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/external_wallets"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "id": "external-1",
+            "name": "Exchange",
+            "assets": [
+                { "id": "BTC_TEST", "address": "0x000000000000000000000000000000000000dead" },
+                { "id": "ETH_TEST5", "address": "0x000000000000000000000000000000000000dead" }
+            ]
+        }])))
+        .mount(&server)
+        .await;
+    mock_transfer_to(
+        &server,
+        "tx-external",
+        json!({ "type": "EXTERNAL_WALLET", "id": "external-1" }),
+    )
+    .await;
+
+    let tx = TransactionRequest::default()
+        .from(VAULT_ADDRESS)
+        .to(address!("000000000000000000000000000000000000dEaD"))
+        .value(U256::from(1));
+    provider.create_fireblocks_transaction(&tx).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_create_fireblocks_transaction_to_contract_wallet() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/contracts"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "id": "contract-1",
+            "name": "Token",
+            "assets": [{ "id": "ETH_TEST5", "address": "0x000000000000000000000000000000000000dead" }]
+        }])))
+        .mount(&server)
+        .await;
+    mock_transfer_to(
+        &server,
+        "tx-contract",
+        json!({ "type": "CONTRACT", "id": "contract-1" }),
+    )
+    .await;
+
+    let tx = TransactionRequest::default()
+        .from(VAULT_ADDRESS)
+        .to(address!("000000000000000000000000000000000000dEaD"))
+        .value(U256::from(1));
+    provider.create_fireblocks_transaction(&tx).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_address_book_caching() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/internal_wallets"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "id": "internal-1",
+            "name": "Treasury",
+            "assets": [{ "id": "ETH_TEST5", "address": "0x000000000000000000000000000000000000dead" }]
+        }])))
+        .mount(&server)
+        .await;
+
+    let to = address!("000000000000000000000000000000000000dEaD");
+    for _ in 0..2 {
+        let destination = provider.resolve_destination(&to).await.unwrap();
+        assert_eq!(destination.peer_type, PeerType::INTERNAL_WALLET);
+        assert_eq!(destination.id.as_deref(), Some("internal-1"));
+    }
+
+    // The address book is only loaded once until refreshed
+    let count_loads = |requests: Vec<Request>| {
+        requests
+            .iter()
+            .filter(|req| req.url.path() == "/v1/internal_wallets")
+            .count()
+    };
+    assert_eq!(count_loads(server.received_requests().await.unwrap()), 1);
+
+    let address_book = provider.refresh_address_book().await.unwrap();
+    assert_eq!(address_book.len(), 2);
+    assert_eq!(count_loads(server.received_requests().await.unwrap()), 2);
+}
+
+#[tokio::test]
+async fn test_address_book_refreshed_on_miss() {
+    let server = MockServer::start().await;
+    let mut provider = mock_provider(&server).await;
+    provider.config = provider
+        .config
+        .clone()
+        .with_address_book_refresh_interval(300);
+
+    let internal_wallet = |address: &str| {
+        json!({
+            "id": format!("internal-{}", &address[38..]),
+            "name": "Treasury",
+            "assets": [{ "id": "ETH_TEST5", "address": address }]
+        })
+    };
+    let first = "0x000000000000000000000000000000000000dead";
+    let whitelisted = "0x000000000000000000000000000000000000beef";
+
+    // The second wallet is whitelisted after the address book was loaded
+    Mock::given(method("GET"))
+        .and(path("/v1/internal_wallets"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([internal_wallet(first)])))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/internal_wallets"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            internal_wallet(first),
+            internal_wallet(whitelisted)
+        ])))
+        .with_priority(2)
+        .mount(&server)
+        .await;
+
+    let count_loads = |requests: Vec<Request>| {
+        requests
+            .iter()
+            .filter(|req| req.url.path() == "/v1/internal_wallets")
+            .count()
+    };
+    let whitelisted = whitelisted.parse().unwrap();
+
+    let destination = provider
+        .resolve_destination(&first.parse().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(destination.id.as_deref(), Some("internal-dead"));
+
+    // Misses within the refresh interval use the loaded address book
+    let destination = provider.resolve_destination(&whitelisted).await.unwrap();
+    assert_eq!(destination.peer_type, PeerType::ONE_TIME_ADDRESS);
+    assert_eq!(count_loads(server.received_requests().await.unwrap()), 1);
+
+    // Once it is over, a miss reloads the address book
+    tokio::time::sleep(Duration::from_millis(350)).await;
+    let destination = provider.resolve_destination(&whitelisted).await.unwrap();
+    assert_eq!(destination.peer_type, PeerType::INTERNAL_WALLET);
+    assert_eq!(destination.id.as_deref(), Some("internal-beef"));
+    assert_eq!(count_loads(server.received_requests().await.unwrap()), 2);
+
+    // A second miss right after does not reload it again
+    let unknown = address!("000000000000000000000000000000000000f00d");
+    for _ in 0..2 {
+        let destination = provider.resolve_destination(&unknown).await.unwrap();
+        assert_eq!(destination.peer_type, PeerType::ONE_TIME_ADDRESS);
+    }
+    assert_eq!(count_loads(server.received_requests().await.unwrap()), 2);
+}
+
+#[tokio::test]
+async fn test_create_fireblocks_transaction_one_time_addresses_disabled() {
+    let server = MockServer::start().await;
+    let mut provider = mock_provider(&server).await;
+    provider.config = provider
+        .config
+        .clone()
+        .with_one_time_addresses_enabled(false);
+
+    let tx = TransactionRequest::default()
+        .from(VAULT_ADDRESS)
+        .to(address!("000000000000000000000000000000000000dEaD"))
+        .value(U256::from(1));
+
+    match provider.create_fireblocks_transaction(&tx).await {
+        Err(FireblocksError::UnresolvedDestinationError(address)) => {
+            assert_eq!(address, "0x000000000000000000000000000000000000dEaD");
+        }
+        other => panic!("Expected an unresolved destination, got {:?}", other),
+    }
+
+    // Nothing was submitted to Fireblocks
+    let requests = server.received_requests().await.unwrap();
+    assert!(requests.iter().all(|req| req.method.as_str() == "GET"));
+}
+
+#[tokio::test]
+async fn test_create_fireblocks_transaction_failed() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/transactions"))
+        .and(body_partial_json(json!({ "operation": "TRANSFER" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "tx-2",
+            "status": "SUBMITTED"
+        })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/transactions/tx-2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "tx-2",
+            "assetId": "ETH_TEST5",
+            "status": "REJECTED",
+            "subStatus": "REJECTED_BY_USER"
+        })))
+        .mount(&server)
+        .await;
+
+    let tx = TransactionRequest::default()
+        .from(VAULT_ADDRESS)
+        .to(address!("000000000000000000000000000000000000dEaD"))
+        .value(U256::from(1));
+
+    match provider.create_fireblocks_transaction(&tx).await {
+        Err(FireblocksError::TransactionFailedError(id, _, sub_status)) => {
+            assert_eq!(id, "tx-2");
+            assert_eq!(sub_status, "REJECTED_BY_USER");
+        }
+        other => panic!("Expected a failed transaction, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_create_fireblocks_transaction_unknown_account() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+
+    let tx = TransactionRequest::default()
+        .from(address!("000000000000000000000000000000000000bEEF"))
+        .to(VAULT_ADDRESS);
+
+    assert!(matches!(
+        provider.create_fireblocks_transaction(&tx).await,
+        Err(FireblocksError::UnknownAccountError(_))
+    ));
+}
+
+// Helper to mock the status of a Fireblocks transaction, optionally only for the next `times` polls
+async fn mock_tx_status(server: &MockServer, tx_id: &str, status: &str, times: Option<u64>) {
+    let mock = Mock::given(method("GET"))
+        .and(path(format!("/v1/transactions/{}", tx_id)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": tx_id,
+            "assetId": "ETH_TEST5",
+            "status": status,
+            "subStatus": if status == "BLOCKED" { "BLOCKED_BY_POLICY" } else { "" },
+            "txHash": "0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060"
+        })));
+    match times {
+        Some(times) => mock.up_to_n_times(times).mount(server).await,
+        None => mock.mount(server).await,
+    }
+}
+
+#[test]
+fn test_transaction_status_classification() {
+    for status in [
+        TransactionStatus::CANCELLED,
+        TransactionStatus::REJECTED,
+        TransactionStatus::FAILED,
+        TransactionStatus::TIMEOUT,
+        TransactionStatus::BLOCKED,
+    ] {
+        assert!(status.is_final() && status.is_failed() && !status.is_successful());
+    }
+    for status in [
+        TransactionStatus::SUBMITTED,
+        TransactionStatus::PENDING_SIGNATURE,
+        TransactionStatus::BROADCASTING,
+        TransactionStatus::CONFIRMING,
+        TransactionStatus::CANCELLING,
+    ] {
+        assert!(!status.is_final());
+    }
+    assert!(
+        TransactionStatus::COMPLETED.is_final() && TransactionStatus::COMPLETED.is_successful()
+    );
+}
+
+#[tokio::test]
+async fn test_wait_for_transaction_completed() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+
+    mock_tx_status(&server, "tx-poll", "PENDING_SIGNATURE", Some(1)).await;
+    mock_tx_status(&server, "tx-poll", "BROADCASTING", Some(1)).await;
+    mock_tx_status(&server, "tx-poll", "COMPLETED", None).await;
+
+    let outcome = provider.wait_for_transaction("tx-poll").await.unwrap();
+    assert!(outcome.is_success());
+    assert_eq!(outcome.status(), &TransactionStatus::COMPLETED);
+    assert_eq!(outcome.sub_status(), None);
+    assert_eq!(server.received_requests().await.unwrap().len(), 4);
+}
+
+#[tokio::test]
+async fn test_wait_for_transaction_blocked() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+
+    mock_tx_status(&server, "tx-blocked", "PENDING_AML_SCREENING", Some(1)).await;
+    mock_tx_status(&server, "tx-blocked", "BLOCKED", None).await;
+
+    let outcome = provider.wait_for_transaction("tx-blocked").await.unwrap();
+    assert!(!outcome.is_success());
+    assert_eq!(outcome.status(), &TransactionStatus::BLOCKED);
+    assert_eq!(outcome.sub_status(), Some("BLOCKED_BY_POLICY"));
+    assert!(matches!(
+        outcome.into_result(),
+        Err(FireblocksError::TransactionFailedError(
+            _,
+            TransactionStatus::BLOCKED,
+            _
+        ))
+    ));
+}
+
+#[tokio::test]
+async fn test_wait_for_transaction_timeout() {
+    let server = MockServer::start().await;
+    let mut provider = mock_provider(&server).await;
+    provider.config = provider.config.clone().with_polling_timeout(50);
+
+    mock_tx_status(&server, "tx-stuck", "PENDING_AUTHORIZATION", None).await;
+
+    match provider.wait_for_transaction("tx-stuck").await {
+        Err(FireblocksError::TransactionTimeoutError(id, status)) => {
+            assert_eq!(id, "tx-stuck");
+            assert_eq!(status, TransactionStatus::PENDING_AUTHORIZATION);
+        }
+        other => panic!("Expected a timeout, got {:?}", other),
+    }
+}
+
+// Generic helper mirroring code written against any alloy provider
+async fn balance_of<P: Provider>(provider: &P, address: Address) -> U256 {
+    provider.get_balance(address).await.unwrap()
+}
+
+// Helper to mock a broadcast transaction with the given status and hash
+async fn mock_broadcast_tx(server: &MockServer, tx_id: &str, status: &str, tx_hash: &str) {
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/transactions/{}", tx_id)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": tx_id,
+            "assetId": "ETH_TEST5",
+            "operation": "CONTRACT_CALL",
+            "status": status,
+            "txHash": tx_hash,
+            "source": { "type": "VAULT_ACCOUNT", "id": "0" },
+            "destination": { "type": "ONE_TIME_ADDRESS" },
+            "destinationAddress": "0x000000000000000000000000000000000000dEaD",
+            "amountInfo": { "amount": "0.01", "requestedAmount": "0.01" },
+            "extraParameters": { "contractCallData": "0xdeadbeef" },
+            "note": "stuck"
+        })))
+        .mount(server)
+        .await;
+}
+
+const STUCK_HASH: &str = "0x1111111111111111111111111111111111111111111111111111111111111111";
+const REPLACEMENT_HASH: &str = "0x2222222222222222222222222222222222222222222222222222222222222222";
+
+#[tokio::test]
+async fn test_speed_up_transaction() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+
+    mock_broadcast_tx(&server, "tx-stuck", "BROADCASTING", STUCK_HASH).await;
+    mock_broadcast_tx(&server, "tx-replacement", "COMPLETED", REPLACEMENT_HASH).await;
+    Mock::given(method("POST"))
+        .and(path("/v1/transactions"))
+        .and(body_partial_json(json!({
+            "assetId": "ETH_TEST5",
+            "operation": "CONTRACT_CALL",
+            "source": { "type": "VAULT_ACCOUNT", "id": "0" },
+            "destination": {
+                "type": "ONE_TIME_ADDRESS",
+                "oneTimeAddress": { "address": "0x000000000000000000000000000000000000dEaD" }
+            },
+            "amount": "0.01",
+            "extraParameters": { "contractCallData": "0xdeadbeef" },
+            "feeLevel": "HIGH",
+            "replaceTxByHash": STUCK_HASH
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "tx-replacement",
+            "status": "SUBMITTED"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let outcome = provider
+        .speed_up_transaction("tx-stuck", FeeBump::FeeLevel(FeeLevel::HIGH))
+        .await
+        .unwrap();
+
+    assert!(outcome.replaced());
+    assert_eq!(outcome.original_tx_id, "tx-stuck");
+    assert_eq!(outcome.mined_tx_id, "tx-replacement");
+    assert_eq!(
+        outcome.mined_tx_hash,
+        REPLACEMENT_HASH.parse::<B256>().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_speed_up_transaction_original_mined() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+
+    // The stuck transaction is still confirming when the replacement is
+    // submitted, but is mined before the replacement
+    Mock::given(method("GET"))
+        .and(path("/v1/transactions/tx-stuck"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "tx-stuck",
+            "assetId": "ETH_TEST5",
+            "status": "CONFIRMING",
+            "txHash": STUCK_HASH,
+            "source": { "type": "VAULT_ACCOUNT", "id": "0" },
+            "destination": { "type": "ONE_TIME_ADDRESS" },
+            "destinationAddress": "0x000000000000000000000000000000000000dEaD",
+            "amountInfo": { "amount": "0.01" }
+        })))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    mock_broadcast_tx(&server, "tx-stuck", "COMPLETED", STUCK_HASH).await;
+    Mock::given(method("GET"))
+        .and(path("/v1/transactions/tx-replacement"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "tx-replacement",
+            "assetId": "ETH_TEST5",
+            "status": "FAILED",
+            "subStatus": "NONCE_TOO_LOW"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/transactions"))
+        .and(body_partial_json(json!({
+            "operation": "TRANSFER",
+            "amount": "0.01",
+            "maxFee": "40",
+            "priorityFee": "3",
+            "replaceTxByHash": STUCK_HASH
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "tx-replacement",
+            "status": "SUBMITTED"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let outcome = provider
+        .speed_up_transaction(
+            "tx-stuck",
+            FeeBump::Eip1559 {
+                max_fee: "40".to_string(),
+                priority_fee: "3".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+    assert!(!outcome.replaced());
+    assert_eq!(outcome.replacement_tx_id, "tx-replacement");
+    assert_eq!(outcome.mined_tx_id, "tx-stuck");
+    assert_eq!(outcome.mined_tx_hash, STUCK_HASH.parse::<B256>().unwrap());
+}
+
+#[tokio::test]
+async fn test_speed_up_transaction_not_replaceable() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+
+    mock_broadcast_tx(&server, "tx-done", "COMPLETED", STUCK_HASH).await;
+
+    let outcome = provider
+        .speed_up_transaction("tx-done", FeeBump::FeeLevel(FeeLevel::HIGH))
+        .await;
+    assert!(matches!(
+        outcome,
+        Err(FireblocksError::TransactionNotReplaceableError(
+            _,
+            TransactionStatus::COMPLETED
+        ))
+    ));
+}
+
+#[tokio::test]
+async fn test_provider_get_accounts() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+
+    let accounts = provider.get_accounts().await.unwrap();
+    assert_eq!(accounts, vec![VAULT_ADDRESS]);
+}
+
+#[tokio::test]
+async fn test_provider_delegates_reads() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+    mock_rpc(&server, "eth_getBalance", json!("0x2a")).await;
+
+    assert_eq!(balance_of(&provider, VAULT_ADDRESS).await, U256::from(42));
+}
+
+#[tokio::test]
+async fn test_provider_send_transaction() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+    let tx_hash = b256!("0x0101010101010101010101010101010101010101010101010101010101010101");
+
+    Mock::given(method("POST"))
+        .and(path("/v1/transactions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "tx-3",
+            "status": "SUBMITTED"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/transactions/tx-3"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "tx-3",
+            "assetId": "ETH_TEST5",
+            "txHash": tx_hash.to_string(),
+            "status": "COMPLETED"
+        })))
+        .mount(&server)
+        .await;
+
+    let tx = TransactionRequest::default()
+        .from(VAULT_ADDRESS)
+        .to(address!("000000000000000000000000000000000000dEaD"))
+        .value(U256::from(1));
+
+    let pending = provider.send_transaction(tx).await.unwrap();
+    assert_eq!(*pending.tx_hash(), tx_hash);
+}
+
+#[tokio::test]
+async fn test_request_accounts() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+
+    for rpc_method in ["eth_accounts", "eth_requestAccounts"] {
+        let accounts = provider
+            .request(RequestArguments {
+                method: rpc_method.to_string(),
+                params: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(accounts, json!([VAULT_ADDRESS.to_string()]));
+    }
+}
+
+#[tokio::test]
+async fn test_request_send_transaction_unknown_account() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+
+    let err = provider
+        .request(RequestArguments {
+            method: "eth_sendTransaction".to_string(),
+            params: Some(json!([{
+                "from": "0x000000000000000000000000000000000000bEEF",
+                "to": VAULT_ADDRESS.to_string(),
+                "value": "0x1"
+            }])),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(err.code, ProviderRpcError::UNAUTHORIZED);
+
+    let err = provider
+        .request(RequestArguments {
+            method: "eth_sendTransaction".to_string(),
+            params: None,
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(err.code, ProviderRpcError::INVALID_PARAMS);
+}
+
+// Helper to mock a transfer that Fireblocks fails with the given status and sub status
+async fn mock_failed_transfer(server: &MockServer, tx_id: &str, status: &str, sub_status: &str) {
+    Mock::given(method("POST"))
+        .and(path("/v1/transactions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": tx_id,
+            "status": "SUBMITTED"
+        })))
+        .mount(server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/transactions/{}", tx_id)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": tx_id,
+            "assetId": "ETH_TEST5",
+            "status": status,
+            "subStatus": sub_status
+        })))
+        .mount(server)
+        .await;
+}
+
+// Helper to build an eth_sendTransaction request from the vault to a burn address
+fn send_transaction_request() -> RequestArguments<serde_json::Value> {
+    RequestArguments {
+        method: "eth_sendTransaction".to_string(),
+        params: Some(json!([{
+            "from": VAULT_ADDRESS.to_string(),
+            "to": "0x000000000000000000000000000000000000dEaD",
+            "value": "0x1"
+        }])),
+    }
+}
+
+#[tokio::test]
+async fn test_request_send_transaction_failures_explained() {
+    let cases = [
+        (
+            "FAILED",
+            "INSUFFICIENT_FUNDS",
+            ProviderRpcError::INVALID_INPUT,
+            "insufficient funds",
+        ),
+        (
+            "CANCELLED",
+            "CANCELLED_BY_USER",
+            ProviderRpcError::USER_REJECTED_REQUEST,
+            "cancelled by a user",
+        ),
+        (
+            "BLOCKED",
+            "BLOCKED_BY_POLICY",
+            ProviderRpcError::TRANSACTION_REJECTED,
+            "Transaction Authorization Policy",
+        ),
+        (
+            "REJECTED",
+            "REJECTED_AML_SCREENING",
+            ProviderRpcError::TRANSACTION_REJECTED,
+            "AML screening",
+        ),
+        (
+            "FAILED",
+            "",
+            ProviderRpcError::INTERNAL_ERROR,
+            "failed with status FAILED",
+        ),
+    ];
+
+    for (status, sub_status, code, reason) in cases {
+        let server = MockServer::start().await;
+        let provider = mock_provider(&server).await;
+        mock_failed_transfer(&server, "tx-9", status, sub_status).await;
+
+        let err = provider
+            .request(send_transaction_request())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, code, "{}", sub_status);
+        assert!(err.message.contains(reason), "{}", err.message);
+        assert!(
+            err.message
+                .contains("https://console.fireblocks.io/v2/transactions/tx-9"),
+            "{}",
+            err.message
+        );
+
+        let data: serde_json::Value = serde_json::from_str(&err.data.unwrap()).unwrap();
+        assert_eq!(
+            data,
+            json!({ "txId": "tx-9", "status": status, "subStatus": sub_status })
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_request_api_error_explained() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/transactions"))
+        .respond_with(
+            ResponseTemplate::new(400)
+                .insert_header("X-Request-ID", "request-1")
+                .set_body_json(json!({
+                    "message": "Transaction blocked by the Transaction Authorization Policy",
+                    "code": 1400
+                })),
+        )
+        .mount(&server)
+        .await;
+
+    let err = provider
+        .request(send_transaction_request())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code, ProviderRpcError::TRANSACTION_REJECTED);
+    assert_eq!(
+        err.message,
+        "Fireblocks rejected the request: Transaction blocked by the Transaction Authorization Policy (request id request-1)"
+    );
+}
+
+#[tokio::test]
+async fn test_request_without_enhanced_error_handling() {
+    let server = MockServer::start().await;
+    let mut provider = mock_provider(&server).await;
+    provider.config.enhanced_error_handling = Some(false);
+    mock_failed_transfer(&server, "tx-9", "FAILED", "INSUFFICIENT_FUNDS").await;
+
+    let err = provider
+        .request(send_transaction_request())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code, ProviderRpcError::INTERNAL_ERROR);
+    assert_eq!(
+        err.message,
+        FireblocksError::TransactionFailedError(
+            "tx-9".to_string(),
+            TransactionStatus::FAILED,
+            "INSUFFICIENT_FUNDS".to_string()
+        )
+        .to_string()
+    );
+    assert_eq!(err.data, None);
+}
+
+#[tokio::test]
+async fn test_provider_send_transaction_errors() {
+    let server = MockServer::start().await;
+    let mut provider = mock_provider(&server).await;
+    mock_failed_transfer(&server, "tx-9", "BLOCKED", "BLOCKED_BY_POLICY").await;
+
+    let tx = TransactionRequest::default()
+        .from(VAULT_ADDRESS)
+        .to(address!("000000000000000000000000000000000000dEaD"))
+        .value(U256::from(1));
+
+    // Enhanced errors are JSON-RPC error responses
+    let err = provider.send_transaction(tx.clone()).await.unwrap_err();
+    let payload = err.as_error_resp().unwrap();
+    assert_eq!(payload.code, ProviderRpcError::TRANSACTION_REJECTED as i64);
+    assert!(payload.message.contains("tx-9"));
+    assert!(payload.data.is_some());
+
+    // Otherwise the raw Fireblocks error is passed on
+    provider.config.enhanced_error_handling = Some(false);
+    let err = provider.send_transaction(tx).await.unwrap_err();
+    match err {
+        RpcError::Transport(TransportErrorKind::Custom(error)) => {
+            match error.downcast_ref::<FireblocksError>() {
+                Some(FireblocksError::TransactionFailedError(tx_id, status, _)) => {
+                    assert_eq!(tx_id, "tx-9");
+                    assert_eq!(*status, TransactionStatus::BLOCKED);
+                }
+                other => panic!("Expected a failed transaction, got {:?}", other),
+            }
+        }
+        other => panic!("Expected a custom transport error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_request_forwards_to_rpc() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+    mock_rpc(&server, "eth_chainId", json!("0xaa36a7")).await;
+
+    let chain_id = provider
+        .request(RequestArguments {
+            method: "eth_chainId".to_string(),
+            params: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(chain_id, json!("0xaa36a7"));
+}
+
+#[tokio::test]
+async fn test_request_forwards_rpc_errors() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(body_partial_json(json!({ "method": "eth_call" })))
+        .respond_with(|req: &Request| {
+            let body: serde_json::Value = serde_json::from_slice(&req.body).unwrap();
+            ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": body["id"],
+                "error": { "code": 3, "message": "execution reverted", "data": "0x" }
+            }))
+        })
+        .mount(&server)
+        .await;
+
+    let err = provider
+        .request(RequestArguments {
+            method: "eth_call".to_string(),
+            params: Some(json!([{ "to": VAULT_ADDRESS.to_string() }, "latest"])),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(err.code, 3);
+    assert_eq!(err.message, "execution reverted");
+    assert_eq!(err.data, Some("\"0x\"".to_string()));
+}
+
+#[tokio::test]
+async fn test_sign_message() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+    let message = b"Sign in with Ethereum";
+
+    Mock::given(method("POST"))
+        .and(path("/v1/transactions"))
+        .and(body_partial_json(json!({
+            "operation": "RAW",
+            "source": { "type": "VAULT_ACCOUNT", "id": "0" },
+            "extraParameters": {
+                "rawMessageData": {
+                    "messages": [{ "content": hex::encode(message), "type": "ETH_MESSAGE" }]
+                }
+            }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "sign-1",
+            "status": "SUBMITTED"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    mock_signing_tx(
+        &server,
+        "sign-1",
+        vault_signature(eip191_hash_message(message)),
+    )
+    .await;
+
+    let signature = provider
+        .sign_message(&VAULT_ADDRESS, message)
+        .await
+        .unwrap();
+    assert_eq!(
+        signature.recover_address_from_msg(message).unwrap(),
+        VAULT_ADDRESS
+    );
+    assert!([27, 28].contains(&signature.as_bytes()[64]));
+}
+
+#[tokio::test]
+async fn test_sign_message_mismatch() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+
+    // Signature over a different message recovers to another address
+    mock_signing_tx(
+        &server,
+        "sign-2",
+        vault_signature(eip191_hash_message(b"other")),
+    )
+    .await;
+
+    assert!(matches!(
+        provider.sign_message(&VAULT_ADDRESS, b"message").await,
+        Err(FireblocksError::SignatureMismatchError(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_request_personal_sign() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+    let message = b"hello";
+    mock_signing_tx(
+        &server,
+        "sign-3",
+        vault_signature(eip191_hash_message(message)),
+    )
+    .await;
+
+    for (rpc_method, params) in [
+        (
+            "personal_sign",
+            json!([hex::encode_prefixed(message), VAULT_ADDRESS.to_string()]),
+        ),
+        ("eth_sign", json!([VAULT_ADDRESS.to_string(), "hello"])),
+    ] {
+        let signature = provider
+            .request(RequestArguments {
+                method: rpc_method.to_string(),
+                params: Some(params),
+            })
+            .await
+            .unwrap();
+
+        let signature: PrimitiveSignature = signature.as_str().unwrap().parse().unwrap();
+        assert_eq!(
+            signature.recover_address_from_msg(message).unwrap(),
+            VAULT_ADDRESS
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_raw_request_routes_signing_through_fireblocks() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+    let message = b"hello";
+    mock_signing_tx(
+        &server,
+        "sign-4",
+        vault_signature(eip191_hash_message(message)),
+    )
+    .await;
+    mock_rpc(&server, "eth_chainId", json!("0xaa36a7")).await;
+
+    let accounts: Vec<Address> = provider
+        .raw_request("eth_accounts".into(), ())
+        .await
+        .unwrap();
+    assert_eq!(accounts, vec![VAULT_ADDRESS]);
+
+    let signature: String = provider
+        .raw_request(
+            "personal_sign".into(),
+            (hex::encode_prefixed(message), VAULT_ADDRESS),
+        )
+        .await
+        .unwrap();
+    let signature: PrimitiveSignature = signature.parse().unwrap();
+    assert_eq!(
+        signature.recover_address_from_msg(message).unwrap(),
+        VAULT_ADDRESS
+    );
+
+    // Only the node answers other methods
+    let chain_id: String = provider
+        .raw_request("eth_chainId".into(), ())
+        .await
+        .unwrap();
+    assert_eq!(chain_id, "0xaa36a7");
+    let rpc_methods: Vec<_> = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter(|req| req.url.path() == "/")
+        .map(|req| {
+            serde_json::from_slice::<serde_json::Value>(&req.body).unwrap()["method"].clone()
+        })
+        .collect();
+    assert_eq!(rpc_methods, vec![json!("eth_chainId")]);
+}
+
+// Helper returning an EIP-2612 permit as EIP-712 typed data
+fn permit_typed_data() -> serde_json::Value {
+    json!({
+        "types": {
+            "EIP712Domain": [
+                { "name": "name", "type": "string" },
+                { "name": "version", "type": "string" },
+                { "name": "chainId", "type": "uint256" },
+                { "name": "verifyingContract", "type": "address" }
+            ],
+            "Permit": [
+                { "name": "owner", "type": "address" },
+                { "name": "spender", "type": "address" },
+                { "name": "value", "type": "uint256" },
+                { "name": "nonce", "type": "uint256" },
+                { "name": "deadline", "type": "uint256" }
+            ]
+        },
+        "primaryType": "Permit",
+        "domain": {
+            "name": "Test Token",
+            "version": "1",
+            "chainId": 11155111,
+            "verifyingContract": "0x000000000000000000000000000000000000cafe"
+        },
+        "message": {
+            "owner": VAULT_ADDRESS.to_string(),
+            "spender": "0x000000000000000000000000000000000000dEaD",
+            "value": "1000",
+            "nonce": "0",
+            "deadline": "1700000000"
+        }
+    })
+}
+
+#[tokio::test]
+async fn test_sign_typed_data() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+    let typed_data: TypedData = serde_json::from_value(permit_typed_data()).unwrap();
+    let signing_hash = typed_data.eip712_signing_hash().unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/v1/transactions"))
+        .and(body_partial_json(json!({
+            "operation": "RAW",
+            "extraParameters": {
+                "rawMessageData": {
+                    "messages": [{ "type": "EIP712", "content": { "primaryType": "Permit" } }]
+                }
+            }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "sign-4",
+            "status": "SUBMITTED"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    mock_signing_tx(&server, "sign-4", vault_signature(signing_hash)).await;
+
+    let signature = provider
+        .sign_typed_data(&VAULT_ADDRESS, &typed_data)
+        .await
+        .unwrap();
+    assert_eq!(
+        signature
+            .recover_address_from_prehash(&signing_hash)
+            .unwrap(),
+        VAULT_ADDRESS
+    );
+}
+
+#[tokio::test]
+async fn test_sign_typed_data_struct() {
+    sol! {
+        #[derive(serde::Serialize)]
+        struct Mail {
+            address to;
+            string contents;
+        }
+    }
+
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+    let mail = Mail {
+        to: address!("000000000000000000000000000000000000dEaD"),
+        contents: "hello".to_string(),
+    };
+    let domain = eip712_domain! {
+        name: "Mail",
+        version: "1",
+        chain_id: 11155111,
+    };
+    mock_signing_tx(
+        &server,
+        "sign-5",
+        vault_signature(mail.eip712_signing_hash(&domain)),
+    )
+    .await;
+
+    let signature = provider
+        .sign_typed_data_struct(&VAULT_ADDRESS, &mail, &domain)
+        .await
+        .unwrap();
+    assert_eq!(
+        signature
+            .recover_address_from_prehash(&mail.eip712_signing_hash(&domain))
+            .unwrap(),
+        VAULT_ADDRESS
+    );
+}
+
+#[tokio::test]
+async fn test_request_sign_typed_data_v4() {
+    let server = MockServer::start().await;
+    let provider = mock_provider(&server).await;
+    let typed_data: TypedData = serde_json::from_value(permit_typed_data()).unwrap();
+    let signing_hash = typed_data.eip712_signing_hash().unwrap();
+    mock_signing_tx(&server, "sign-6", vault_signature(signing_hash)).await;
+
+    let signature = provider
+        .request(RequestArguments {
+            method: "eth_signTypedData_v4".to_string(),
+            params: Some(json!([
+                VAULT_ADDRESS.to_string(),
+                permit_typed_data().to_string()
+            ])),
+        })
+        .await
+        .unwrap();
+
+    let signature: PrimitiveSignature = signature.as_str().unwrap().parse().unwrap();
+    assert_eq!(
+        signature
+            .recover_address_from_prehash(&signing_hash)
+            .unwrap(),
+        VAULT_ADDRESS
+    );
+}
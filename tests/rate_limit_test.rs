@@ -0,0 +1,168 @@
+use std::time::{Duration, Instant};
+
+use reqwest::Method;
+use serde_json::json;
+use tokio::fs;
+use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+use alloy_fireblocks::{
+    api::FireblocksClient,
+    rate_limit::{EndpointClass, RateLimiter},
+    types::{ApiBaseUrl, FireblocksError, RateLimit, RateLimits},
+};
+
+// Helper to create a client rate limited by the given limiter
+async fn limited_client(server: &MockServer, rate_limiter: RateLimiter) -> FireblocksClient {
+    let private_key = fs::read_to_string("tests/fixtures/test_private_key.pem")
+        .await
+        .unwrap();
+
+    FireblocksClient::builder(
+        private_key,
+        "test-api-key".to_string(),
+        ApiBaseUrl::Custom(server.uri()),
+    )
+    .with_rate_limiter(rate_limiter)
+    .build()
+    .unwrap()
+}
+
+// Helper to mock successful responses to every request
+async fn mock_ok(server: &MockServer) {
+    for http_method in ["GET", "POST"] {
+        Mock::given(method(http_method))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .mount(server)
+            .await;
+    }
+}
+
+#[test]
+fn test_endpoint_classes() {
+    assert_eq!(
+        EndpointClass::of(&Method::GET, "/v1/transactions?limit=10"),
+        EndpointClass::Read
+    );
+    assert_eq!(
+        EndpointClass::of(&Method::POST, "/v1/transactions"),
+        EndpointClass::CreateTransaction
+    );
+    assert_eq!(
+        EndpointClass::of(&Method::POST, "/v1/transactions/tx-1/cancel"),
+        EndpointClass::Write
+    );
+    assert_eq!(
+        EndpointClass::of(&Method::POST, "/v1/vault/accounts"),
+        EndpointClass::Write
+    );
+}
+
+#[tokio::test]
+async fn test_rate_limiter_waits_for_tokens() {
+    let server = MockServer::start().await;
+    mock_ok(&server).await;
+    let limiter =
+        RateLimiter::new(&RateLimits::default().with_read(RateLimit::new(10.0, 2).unwrap()))
+            .unwrap();
+    let client = limited_client(&server, limiter).await;
+
+    // The burst goes through at once, the third request waits for a refill
+    let start = Instant::now();
+    for _ in 0..3 {
+        client.get_internal_wallets().await.unwrap();
+    }
+    assert!(start.elapsed() >= Duration::from_millis(80));
+
+    let metrics = client.rate_limiter().unwrap().metrics(EndpointClass::Read);
+    assert_eq!(metrics.requests, 3);
+    assert_eq!(metrics.throttled_requests, 1);
+    assert!(metrics.total_wait > Duration::ZERO);
+    assert_eq!(metrics.total_wait, metrics.max_wait);
+}
+
+#[tokio::test]
+async fn test_rate_limiter_shared_across_clones() {
+    let server = MockServer::start().await;
+    mock_ok(&server).await;
+    let limiter =
+        RateLimiter::new(&RateLimits::default().with_read(RateLimit::new(20.0, 1).unwrap()))
+            .unwrap();
+    let client = limited_client(&server, limiter.clone()).await;
+    let cloned = client.clone();
+    let other = limited_client(&server, limiter.clone()).await;
+
+    let (first, second, third) = tokio::join!(
+        client.get_internal_wallets(),
+        cloned.get_internal_wallets(),
+        other.get_internal_wallets()
+    );
+    first.unwrap();
+    second.unwrap();
+    third.unwrap();
+
+    let metrics = limiter.metrics(EndpointClass::Read);
+    assert_eq!(metrics.requests, 3);
+    assert_eq!(metrics.throttled_requests, 2);
+    assert!(metrics.max_wait >= Duration::from_millis(90));
+}
+
+#[tokio::test]
+async fn test_rate_limiter_classes_are_independent() {
+    let server = MockServer::start().await;
+    mock_ok(&server).await;
+    let limiter = RateLimiter::new(
+        &RateLimits::default()
+            .with_read(RateLimit::new(0.1, 1).unwrap())
+            .with_create_transaction(RateLimit::new(1.0, 1).unwrap()),
+    )
+    .unwrap();
+    let client = limited_client(&server, limiter).await;
+
+    // Reads and writes each have their own bucket, and writes are unlimited
+    client.get_internal_wallets().await.unwrap();
+    client.post_request("/v1/transactions", "{}").await.unwrap();
+    for _ in 0..3 {
+        client
+            .post_request("/v1/vault/accounts", "{}")
+            .await
+            .unwrap();
+    }
+
+    let limiter = client.rate_limiter().unwrap();
+    assert_eq!(limiter.metrics(EndpointClass::Read).throttled_requests, 0);
+    assert_eq!(
+        limiter
+            .metrics(EndpointClass::CreateTransaction)
+            .throttled_requests,
+        0
+    );
+    assert_eq!(limiter.metrics(EndpointClass::Write), Default::default());
+}
+
+#[test]
+fn test_rate_limit_rejects_blocking_values() {
+    for (requests_per_second, burst, key) in [
+        (0.0, 1, "requests_per_second"),
+        (-1.0, 1, "requests_per_second"),
+        (f64::NAN, 1, "requests_per_second"),
+        (f64::INFINITY, 1, "requests_per_second"),
+        (1.0, 0, "burst"),
+    ] {
+        match RateLimit::new(requests_per_second, burst) {
+            Err(FireblocksError::ConfigError(name, _)) => assert_eq!(name, key),
+            other => panic!("Expected a config error for {}, got {:?}", key, other),
+        }
+    }
+
+    // Limits built field by field are checked by the limiter
+    let limits = RateLimits::default().with_write(RateLimit {
+        requests_per_second: 0.0,
+        burst: 1,
+    });
+    match RateLimiter::new(&limits) {
+        Err(FireblocksError::ConfigError(name, _)) => {
+            assert_eq!(name, "write.requests_per_second")
+        }
+        other => panic!("Expected a config error, got {:?}", other),
+    }
+}
@@ -0,0 +1,153 @@
+use std::sync::Arc;
+
+use jsonwebtoken::{decode, decode_header, DecodingKey, Validation};
+use serde_json::json;
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+use alloy_fireblocks::{
+    api::FireblocksClient,
+    request_signer::{ExternalSigner, RequestSigner, RsaKeySigner},
+    types::{ApiBaseUrl, FireblocksError},
+};
+
+const PRIVATE_KEY_PATH: &str = "tests/fixtures/test_private_key.pem";
+
+// Helper to check a JWT is signed by the test key, returning its claims
+fn verify_jwt(token: &str) -> serde_json::Value {
+    let public_key = std::fs::read("tests/fixtures/test_public_key.pem").unwrap();
+    decode::<serde_json::Value>(
+        token,
+        &DecodingKey::from_rsa_pem(&public_key).unwrap(),
+        &Validation::new(jsonwebtoken::Algorithm::RS256),
+    )
+    .unwrap()
+    .claims
+}
+
+#[tokio::test]
+async fn test_built_in_signers() {
+    let pem = std::fs::read_to_string(PRIVATE_KEY_PATH).unwrap();
+    let signers = [
+        RsaKeySigner::from_pem(pem).unwrap(),
+        RsaKeySigner::from_pem_file(PRIVATE_KEY_PATH).unwrap(),
+        RsaKeySigner::from_der(&std::fs::read("tests/fixtures/test_private_key.der").unwrap())
+            .unwrap(),
+        RsaKeySigner::from_pkcs8_der(
+            &std::fs::read("tests/fixtures/test_private_key_pkcs8.der").unwrap(),
+        )
+        .unwrap(),
+    ];
+
+    for signer in signers {
+        let client =
+            FireblocksClient::from_signer(Arc::new(signer), "test-api-key", ApiBaseUrl::Sandbox);
+        let token = client
+            .sign_jwt("/v1/transactions", Some("{}"))
+            .await
+            .unwrap();
+
+        let claims = verify_jwt(&token);
+        assert_eq!(claims["uri"], "/v1/transactions");
+        assert_eq!(claims["sub"], "test-api-key");
+        assert_eq!(decode_header(&token).unwrap().kid, None);
+    }
+}
+
+#[tokio::test]
+async fn test_signer_key_id() {
+    let signer = RsaKeySigner::from_pem_file(PRIVATE_KEY_PATH)
+        .unwrap()
+        .with_key_id("key-1".to_string());
+    let client =
+        FireblocksClient::from_signer(Arc::new(signer), "test-api-key", ApiBaseUrl::Sandbox);
+
+    let token = client.sign_jwt("/v1/vault/accounts", None).await.unwrap();
+    assert_eq!(decode_header(&token).unwrap().kid.as_deref(), Some("key-1"));
+    verify_jwt(&token);
+}
+
+#[test]
+fn test_missing_pem_file() {
+    match RsaKeySigner::from_pem_file("tests/fixtures/missing.pem") {
+        Err(FireblocksError::PrivateKeyError(message)) => {
+            assert!(message.contains("missing.pem"), "{}", message)
+        }
+        other => panic!("Expected a private key error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_keys_rejected() {
+    let results = [
+        RsaKeySigner::from_pem("not a key"),
+        RsaKeySigner::from_der(b"not a key"),
+        RsaKeySigner::from_pkcs8_der(b"not a key"),
+        RsaKeySigner::from_pem_file("tests/fixtures/test_public_key.pem"),
+    ];
+    for result in results {
+        assert!(matches!(result, Err(FireblocksError::PrivateKeyError(_))));
+    }
+}
+
+#[tokio::test]
+async fn test_external_signer() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/internal_wallets"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Stands in for a KMS holding the key
+    let kms = Arc::new(RsaKeySigner::from_pem_file(PRIVATE_KEY_PATH).unwrap());
+    let signer = ExternalSigner::new(move |message| {
+        let kms = kms.clone();
+        async move { kms.sign(&message).await }
+    })
+    .with_key_id("kms-key".to_string());
+
+    let client = FireblocksClient::builder_from_signer(
+        Arc::new(signer),
+        "test-api-key",
+        ApiBaseUrl::Custom(server.uri()),
+    )
+    .build()
+    .unwrap();
+    client.get_internal_wallets().await.unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let authorization = requests[0].headers.get("authorization").unwrap();
+    let token = authorization
+        .to_str()
+        .unwrap()
+        .strip_prefix("Bearer ")
+        .unwrap();
+    assert_eq!(
+        decode_header(token).unwrap().kid.as_deref(),
+        Some("kms-key")
+    );
+    assert_eq!(verify_jwt(token)["uri"], "/v1/internal_wallets");
+}
+
+#[tokio::test]
+async fn test_external_signer_failure() {
+    let server = MockServer::start().await;
+    let signer = ExternalSigner::new(|_| async {
+        Err(FireblocksError::SignJWTError("HSM unavailable".to_string()))
+    });
+    let client = FireblocksClient::from_signer(
+        Arc::new(signer),
+        "test-api-key",
+        ApiBaseUrl::Custom(server.uri()),
+    );
+
+    match client.get_internal_wallets().await {
+        Err(FireblocksError::SignJWTError(message)) => assert_eq!(message, "HSM unavailable"),
+        other => panic!("Expected a signing error, got {:?}", other),
+    }
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
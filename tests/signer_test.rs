@@ -0,0 +1,179 @@
+mod common;
+
+use std::time::Duration;
+
+use alloy_consensus::{SignableTransaction, TxEip1559};
+use alloy_core::primitives::{address, b256, eip191_hash_message, hex, TxKind, U256};
+use alloy_network::TxSigner;
+use alloy_signer::Signer;
+use serde_json::json;
+use wiremock::{
+    matchers::{body_partial_json, method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+use alloy_fireblocks::signer::FireblocksSigner;
+use common::*;
+
+// Helper to create a signer for vault 0 backed by a local mock Fireblocks server
+async fn mock_signer(server: &MockServer) -> FireblocksSigner {
+    FireblocksSigner::new(
+        mock_client(server).await,
+        0,
+        "ETH_TEST5".to_string(),
+        VAULT_ADDRESS,
+    )
+    .with_chain_id(Some(11155111))
+    .with_polling_interval(Duration::from_millis(10))
+}
+
+#[tokio::test]
+async fn test_signer_from_vault() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/vault/accounts/0/ETH_TEST5/addresses"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "assetId": "ETH_TEST5",
+            "address": VAULT_ADDRESS.to_string(),
+            "type": "Permanent"
+        }])))
+        .mount(&server)
+        .await;
+
+    let signer = FireblocksSigner::from_vault(mock_client(&server).await, 0, "ETH_TEST5".into())
+        .await
+        .unwrap();
+
+    assert_eq!(Signer::address(&signer), VAULT_ADDRESS);
+    assert_eq!(signer.vault_id(), 0);
+    assert_eq!(signer.chain_id(), None);
+}
+
+#[tokio::test]
+async fn test_signer_sign_hash() {
+    let server = MockServer::start().await;
+    let signer = mock_signer(&server).await;
+
+    let hash = b256!("0x2c7a1b8e24e76a2a5cc2b0a8bb9f7e4d0e21de7f5d0c1c4f6a1de4c38f3b7a10");
+    mock_signing_tx(&server, "tx-sign-hash", vault_signature(hash)).await;
+
+    let signature = signer.sign_hash(&hash).await.unwrap();
+    assert_eq!(
+        signature.recover_address_from_prehash(&hash).unwrap(),
+        VAULT_ADDRESS
+    );
+
+    // The hash is signed as is, without a message type
+    let requests = server.received_requests().await.unwrap();
+    let body: serde_json::Value = requests
+        .iter()
+        .find(|req| req.method.as_str() == "POST")
+        .map(|req| serde_json::from_slice(&req.body).unwrap())
+        .unwrap();
+    let message = &body["extraParameters"]["rawMessageData"]["messages"][0];
+    assert_eq!(message["content"], json!(hex::encode(hash)));
+    assert!(message.get("type").is_none());
+}
+
+#[tokio::test]
+async fn test_signer_sign_message() {
+    let server = MockServer::start().await;
+    let signer = mock_signer(&server).await;
+
+    let message = b"hello fireblocks";
+    mock_signing_tx(
+        &server,
+        "tx-sign-message",
+        vault_signature(eip191_hash_message(message)),
+    )
+    .await;
+
+    let signature = signer.sign_message(message).await.unwrap();
+    assert_eq!(
+        signature.recover_address_from_msg(message).unwrap(),
+        VAULT_ADDRESS
+    );
+}
+
+#[tokio::test]
+async fn test_signer_sign_transaction() {
+    let server = MockServer::start().await;
+    let signer = mock_signer(&server).await;
+
+    let mut tx = TxEip1559 {
+        chain_id: 11155111,
+        nonce: 7,
+        gas_limit: 21_000,
+        max_fee_per_gas: 20_000_000_000,
+        max_priority_fee_per_gas: 1_000_000_000,
+        to: TxKind::Call(address!("000000000000000000000000000000000000dEaD")),
+        value: U256::from(1_000_000_000_000_000u64),
+        ..Default::default()
+    };
+    mock_signing_tx(
+        &server,
+        "tx-sign-transaction",
+        vault_signature(tx.signature_hash()),
+    )
+    .await;
+
+    let signature = TxSigner::sign_transaction(&signer, &mut tx).await.unwrap();
+    assert_eq!(
+        signature
+            .recover_address_from_prehash(&tx.signature_hash())
+            .unwrap(),
+        VAULT_ADDRESS
+    );
+}
+
+#[tokio::test]
+async fn test_signer_sign_transaction_chain_id_mismatch() {
+    let server = MockServer::start().await;
+    let signer = mock_signer(&server).await;
+
+    let mut tx = TxEip1559 {
+        chain_id: 1,
+        ..Default::default()
+    };
+
+    let result = TxSigner::sign_transaction(&signer, &mut tx).await;
+    assert!(matches!(
+        result,
+        Err(alloy_signer::Error::TransactionChainIdMismatch {
+            signer: 11155111,
+            tx: 1
+        })
+    ));
+
+    // Nothing is sent to Fireblocks
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_signer_rejected() {
+    let server = MockServer::start().await;
+    let signer = mock_signer(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/transactions"))
+        .and(body_partial_json(json!({ "operation": "RAW" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "tx-rejected",
+            "status": "SUBMITTED"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/transactions/tx-rejected"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "tx-rejected",
+            "assetId": "ETH_TEST5",
+            "status": "REJECTED",
+            "subStatus": "REJECTED_BY_USER"
+        })))
+        .mount(&server)
+        .await;
+
+    let result = signer.sign_message(b"hello fireblocks").await;
+    assert!(result.unwrap_err().to_string().contains("tx-rejected"));
+}
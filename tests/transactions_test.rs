@@ -0,0 +1,531 @@
+mod common;
+
+use futures::TryStreamExt;
+use serde_json::json;
+use wiremock::{
+    matchers::{body_json, header, method, path, query_param, query_param_is_missing},
+    Mock, MockServer, ResponseTemplate,
+};
+
+use alloy_fireblocks::api::CREATE_TX_OPERATION;
+use alloy_fireblocks::types::{
+    idempotency_key_for, DestinationTransferPeerPath, DropTransactionRequest, FeeLevel, PeerType,
+    RequestOptions, SortDirection, TransactionArguments, TransactionDestination,
+    TransactionFilters, TransactionOperation, TransactionOrderBy, TransactionStatus,
+    TransferPeerPath,
+};
+use common::*;
+
+// A transaction as returned by the Fireblocks API
+fn transaction_json(id: &str, status: &str) -> serde_json::Value {
+    json!({
+        "id": id,
+        "externalTxId": format!("external-{}", id),
+        "assetId": "ETH_TEST5",
+        "operation": "TRANSFER",
+        "status": status,
+        "subStatus": "",
+        "txHash": "0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060",
+        "source": { "type": "VAULT_ACCOUNT", "id": "0", "name": "Default", "subType": "" },
+        "sourceAddress": VAULT_ADDRESS.to_string(),
+        "destination": { "type": "ONE_TIME_ADDRESS", "name": "N/A", "subType": "" },
+        "destinationAddress": "0x000000000000000000000000000000000000dEaD",
+        "destinationAddressDescription": "",
+        "destinationTag": "",
+        "amountInfo": {
+            "amount": "0.01",
+            "requestedAmount": "0.01",
+            "netAmount": "0.01",
+            "amountUSD": "25.1"
+        },
+        "feeInfo": { "networkFee": "0.000021", "gasPrice": "1" },
+        "feeCurrency": "ETH_TEST5",
+        "blockInfo": { "blockHeight": "5000000", "blockHash": "0xabc" },
+        "numOfConfirmations": 3,
+        "createdAt": 1700000000000u64,
+        "lastUpdated": 1700000060000u64,
+        "createdBy": "user-1",
+        "signedBy": ["signer-1"],
+        "rejectedBy": "",
+        "note": "alloy-fireblocks provider",
+        "signedMessages": []
+    })
+}
+
+#[tokio::test]
+async fn test_get_transaction_by_external_id() {
+    let server = MockServer::start().await;
+    let client = mock_client(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/transactions/external_tx_id/external-tx-1"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(transaction_json("tx-1", "COMPLETED")),
+        )
+        .mount(&server)
+        .await;
+
+    let tx = client
+        .get_transaction_by_external_id("external-tx-1")
+        .await
+        .unwrap();
+
+    assert_eq!(tx.id, "tx-1");
+    assert_eq!(tx.external_tx_id.as_deref(), Some("external-tx-1"));
+    assert_eq!(tx.operation, Some(TransactionOperation::TRANSFER));
+    assert_eq!(tx.status, TransactionStatus::COMPLETED);
+
+    let source = tx.source.unwrap();
+    assert_eq!(source.peer_type, PeerType::VAULT_ACCOUNT);
+    assert_eq!(source.id.as_deref(), Some("0"));
+    assert_eq!(
+        tx.destination.unwrap().peer_type,
+        PeerType::ONE_TIME_ADDRESS
+    );
+
+    let amount_info = tx.amount_info.unwrap();
+    assert_eq!(amount_info.amount.as_deref(), Some("0.01"));
+    assert_eq!(amount_info.amount_usd.as_deref(), Some("25.1"));
+    assert_eq!(
+        tx.fee_info.unwrap().network_fee.as_deref(),
+        Some("0.000021")
+    );
+    assert_eq!(
+        tx.block_info.unwrap().block_height.as_deref(),
+        Some("5000000")
+    );
+    assert_eq!(tx.num_of_confirmations, Some(3));
+    assert_eq!(tx.created_at, Some(1700000000000));
+    assert_eq!(tx.signed_by, vec!["signer-1".to_string()]);
+}
+
+#[tokio::test]
+async fn test_list_transactions_with_filters() {
+    let server = MockServer::start().await;
+    let client = mock_client(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/transactions"))
+        .and(query_param("status", "COMPLETED,FAILED"))
+        .and(query_param("after", "1700000000000"))
+        .and(query_param("orderBy", "createdAt"))
+        .and(query_param("sort", "DESC"))
+        .and(query_param("limit", "2"))
+        .and(query_param("sourceType", "VAULT_ACCOUNT"))
+        .and(query_param("sourceId", "0"))
+        .and(query_param("assets", "ETH_TEST5,USDC_TEST"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            transaction_json("tx-1", "COMPLETED"),
+            transaction_json("tx-2", "FAILED")
+        ])))
+        .mount(&server)
+        .await;
+
+    let filters = TransactionFilters::default()
+        .with_status(vec![
+            TransactionStatus::COMPLETED,
+            TransactionStatus::FAILED,
+        ])
+        .with_after(1700000000000)
+        .with_order(TransactionOrderBy::CreatedAt, SortDirection::DESC)
+        .with_limit(2)
+        .with_source(PeerType::VAULT_ACCOUNT, Some("0".to_string()))
+        .with_assets(vec!["ETH_TEST5".to_string(), "USDC_TEST".to_string()]);
+
+    let txs = client.list_transactions(&filters).await.unwrap();
+    assert_eq!(
+        txs.iter().map(|tx| tx.id.as_str()).collect::<Vec<_>>(),
+        vec!["tx-1", "tx-2"]
+    );
+}
+
+#[tokio::test]
+async fn test_list_transactions_without_filters() {
+    let server = MockServer::start().await;
+    let client = mock_client(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/transactions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .mount(&server)
+        .await;
+
+    let txs = client
+        .list_transactions(&TransactionFilters::default())
+        .await
+        .unwrap();
+    assert!(txs.is_empty());
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests[0].url.query(), None);
+}
+
+#[tokio::test]
+async fn test_transaction_actions() {
+    let server = MockServer::start().await;
+    let client = mock_client(&server).await;
+
+    for action in ["cancel", "freeze", "unfreeze"] {
+        Mock::given(method("POST"))
+            .and(path(format!("/v1/transactions/tx-1/{}", action)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "success": true })))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let request_opts = RequestOptions::default();
+    for result in [
+        client.cancel_transaction("tx-1", &request_opts).await,
+        client.freeze_transaction("tx-1", &request_opts).await,
+        client.unfreeze_transaction("tx-1", &request_opts).await,
+    ] {
+        assert!(result.unwrap().success);
+    }
+}
+
+#[tokio::test]
+async fn test_drop_transaction() {
+    let server = MockServer::start().await;
+    let client = mock_client(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/transactions/tx-1/drop"))
+        .and(body_json(json!({ "feeLevel": "HIGH" })))
+        .and(header("Idempotency-Key", "drop-tx-1"))
+        .and(header("X-End-User-Wallet-Id", "ncw-wallet-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": true,
+            "transactions": ["tx-drop-1"]
+        })))
+        .mount(&server)
+        .await;
+
+    let drop_args = DropTransactionRequest {
+        fee_level: Some(FeeLevel::HIGH),
+        gas_price: None,
+    };
+    let request_opts = RequestOptions::default()
+        .with_idempotency_key("drop-tx-1".to_string())
+        .with_ncw_wallet_id("ncw-wallet-1".to_string());
+    let response = client
+        .drop_transaction("tx-1", &drop_args, &request_opts)
+        .await
+        .unwrap();
+    assert!(response.success);
+    assert_eq!(response.transactions, vec!["tx-drop-1".to_string()]);
+}
+
+#[test]
+fn test_transaction_arguments_builder() {
+    let args = TransactionArguments::builder(
+        "ETH_TEST5".to_string(),
+        TransactionOperation::CONTRACT_CALL,
+        TransferPeerPath {
+            peer_type: PeerType::VAULT_ACCOUNT,
+            id: "0".to_string(),
+        },
+    )
+    .with_one_time_address("0x000000000000000000000000000000000000dEaD".to_string())
+    .with_amount("0.5".to_string())
+    .with_treat_as_gross_amount(true)
+    .with_contract_call_data("0xdeadbeef".to_string())
+    .with_gas_limit("60000".to_string())
+    .with_eip1559_fees("30".to_string(), "2".to_string())
+    .with_fail_on_low_fee(true)
+    .with_external_tx_id("order-42".to_string())
+    .with_customer_ref_id("customer-7".to_string())
+    .with_note("payout".to_string())
+    .build()
+    .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&args).unwrap(),
+        json!({
+            "assetId": "ETH_TEST5",
+            "operation": "CONTRACT_CALL",
+            "source": { "type": "VAULT_ACCOUNT", "id": "0" },
+            "destination": {
+                "type": "ONE_TIME_ADDRESS",
+                "oneTimeAddress": { "address": "0x000000000000000000000000000000000000dEaD" }
+            },
+            "amount": "0.5",
+            "treatAsGrossAmount": true,
+            "extraParameters": { "contractCallData": "0xdeadbeef" },
+            "failOnLowFee": true,
+            "gasLimit": "60000",
+            "maxFee": "30",
+            "priorityFee": "2",
+            "externalTxId": "order-42",
+            "customerRefId": "customer-7",
+            "note": "payout"
+        })
+    );
+}
+
+#[test]
+fn test_transaction_arguments_builder_validation() {
+    let builder = || {
+        TransactionArguments::builder(
+            "ETH_TEST5".to_string(),
+            TransactionOperation::TRANSFER,
+            TransferPeerPath {
+                peer_type: PeerType::VAULT_ACCOUNT,
+                id: "0".to_string(),
+            },
+        )
+    };
+
+    let destination = || DestinationTransferPeerPath {
+        peer_type: PeerType::VAULT_ACCOUNT,
+        id: Some("1".to_string()),
+        one_time_address: None,
+    };
+
+    assert!(builder()
+        .with_destination(destination())
+        .with_destinations(vec![TransactionDestination {
+            amount: "1".to_string(),
+            destination: destination(),
+        }])
+        .build()
+        .is_err());
+    assert!(builder()
+        .with_fee_level(FeeLevel::HIGH)
+        .with_eip1559_fees("30".to_string(), "2".to_string())
+        .build()
+        .is_err());
+    assert!(builder()
+        .with_gas_price("30".to_string())
+        .with_eip1559_fees("30".to_string(), "2".to_string())
+        .build()
+        .is_err());
+    assert!(builder()
+        .with_fee_level(FeeLevel::HIGH)
+        .with_gas_limit("21000".to_string())
+        .build()
+        .is_ok());
+}
+
+#[tokio::test]
+async fn test_transactions_stream_follows_next_page() {
+    let server = MockServer::start().await;
+    let client = mock_client(&server).await;
+
+    let transaction_at = |id: &str, created_at: u64| {
+        let mut tx = transaction_json(id, "COMPLETED");
+        tx["createdAt"] = json!(created_at);
+        tx
+    };
+
+    // Pages share a millisecond, which only the cursor can page through
+    Mock::given(method("GET"))
+        .and(path("/v1/transactions"))
+        .and(query_param("limit", "2"))
+        .and(query_param_is_missing("pageCursor"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header(
+                    "next-page",
+                    format!("{}/v1/transactions?pageCursor=cursor-1", server.uri()),
+                )
+                .set_body_json(json!([
+                    transaction_at("tx-4", 1700000000002),
+                    transaction_at("tx-3", 1700000000002)
+                ])),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/transactions"))
+        .and(query_param("pageCursor", "cursor-1"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("next-page", "/v1/transactions?pageCursor=cursor-2")
+                .set_body_json(json!([
+                    transaction_at("tx-2", 1700000000002),
+                    transaction_at("tx-1", 1700000000001)
+                ])),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/transactions"))
+        .and(query_param("pageCursor", "cursor-2"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("next-page", "")
+                .set_body_json(json!([transaction_at("tx-0", 1700000000000)])),
+        )
+        .mount(&server)
+        .await;
+
+    let filters = TransactionFilters::default()
+        .with_order(TransactionOrderBy::LastUpdated, SortDirection::DESC)
+        .with_limit(2);
+    let txs: Vec<_> = client
+        .transactions_stream(filters)
+        .try_collect()
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = txs.iter().map(|tx| tx.id.as_str()).collect();
+    assert_eq!(ids, ["tx-4", "tx-3", "tx-2", "tx-1", "tx-0"]);
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 3);
+    // The cursor holds the filters, which are not sent again
+    assert_eq!(requests[1].url.query(), Some("pageCursor=cursor-1"));
+}
+
+#[tokio::test]
+async fn test_get_transaction_by_external_id_escapes_id() {
+    let server = MockServer::start().await;
+    let client = mock_client(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/transactions/external_tx_id/a%2Fb%3Fc%23d%20e"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(transaction_json("tx-1", "COMPLETED")),
+        )
+        .mount(&server)
+        .await;
+
+    let tx = client
+        .get_transaction_by_external_id("a/b?c#d e")
+        .await
+        .unwrap();
+
+    assert_eq!(tx.id, "tx-1");
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].url.query(), None);
+}
+
+#[tokio::test]
+async fn test_transaction_id_escaped_in_path() {
+    let server = MockServer::start().await;
+    let client = mock_client(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/transactions/a%2Fb%3Fc"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(transaction_json("tx-1", "COMPLETED")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    for action in ["cancel", "drop"] {
+        Mock::given(method("POST"))
+            .and(path(format!("/v1/transactions/a%2Fb%3Fc/{}", action)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "success": true })))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let request_opts = RequestOptions::default();
+    let drop_args = DropTransactionRequest {
+        fee_level: Some(FeeLevel::HIGH),
+        gas_price: None,
+    };
+    client.get_transaction_by_id("a/b?c").await.unwrap();
+    client
+        .cancel_transaction("a/b?c", &request_opts)
+        .await
+        .unwrap();
+    client
+        .drop_transaction("a/b?c", &drop_args, &request_opts)
+        .await
+        .unwrap();
+
+    for request in server.received_requests().await.unwrap() {
+        assert_eq!(request.url.query(), None);
+    }
+}
+
+// Helper to submit a transfer with the given external tx id and request options
+async fn create_transfer(
+    server: &MockServer,
+    external_tx_id: Option<&str>,
+    request_opts: &RequestOptions,
+) -> String {
+    let client = mock_client(server).await;
+    let mut builder = TransactionArguments::builder(
+        "ETH_TEST5".to_string(),
+        TransactionOperation::TRANSFER,
+        TransferPeerPath {
+            peer_type: PeerType::VAULT_ACCOUNT,
+            id: "0".to_string(),
+        },
+    )
+    .with_amount("0.01".to_string());
+    if let Some(external_tx_id) = external_tx_id {
+        builder = builder.with_external_tx_id(external_tx_id.to_string());
+    }
+    client
+        .create_tx(&builder.build().unwrap(), request_opts)
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    requests
+        .last()
+        .unwrap()
+        .headers
+        .get("Idempotency-Key")
+        .map(|key| key.to_str().unwrap().to_string())
+        .unwrap_or_default()
+}
+
+#[tokio::test]
+async fn test_create_tx_idempotency_key() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/transactions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "tx-1",
+            "status": "SUBMITTED"
+        })))
+        .mount(&server)
+        .await;
+
+    // Nothing is derived unless asked for
+    assert_eq!(
+        create_transfer(&server, Some("payout-42"), &RequestOptions::default()).await,
+        ""
+    );
+
+    // Derived from the external tx id, the same on every attempt
+    let derive = RequestOptions::default().with_derived_idempotency_key(true);
+    let derived = create_transfer(&server, Some("payout-42"), &derive).await;
+    assert_eq!(
+        derived,
+        idempotency_key_for(CREATE_TX_OPERATION, "payout-42")
+    );
+    assert_eq!(
+        create_transfer(&server, Some("payout-42"), &derive).await,
+        derived
+    );
+    assert_ne!(
+        idempotency_key_for(CREATE_TX_OPERATION, "payout-43"),
+        derived
+    );
+    assert_eq!(
+        RequestOptions::from_external_id(CREATE_TX_OPERATION, "payout-42").idempotency_key,
+        Some(derived.clone())
+    );
+
+    // The same external id gives another key for another operation
+    assert_ne!(
+        idempotency_key_for("POST /v1/vault/accounts", "payout-42"),
+        derived
+    );
+
+    // An explicit key wins, and no key is derived without an external tx id
+    let request_opts = derive.clone().with_idempotency_key("explicit".to_string());
+    assert_eq!(
+        create_transfer(&server, Some("payout-42"), &request_opts).await,
+        "explicit"
+    );
+    assert_eq!(create_transfer(&server, None, &derive).await, "");
+}
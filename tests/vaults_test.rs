@@ -0,0 +1,331 @@
+mod common;
+
+use futures::TryStreamExt;
+use serde_json::json;
+use wiremock::{
+    matchers::{body_json, header, method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+use alloy_fireblocks::{
+    provider::FireblocksProvider,
+    types::{
+        AssetWalletsFilter, CreateAddressRequest, FireblocksError, RequestOptions, SortDirection,
+        VaultAccountsFilter,
+    },
+};
+use common::*;
+
+// A vault account as returned by the Fireblocks API
+fn vault_json(id: &str, asset_id: &str) -> serde_json::Value {
+    json!({
+        "id": id,
+        "name": format!("Vault {}", id),
+        "hiddenOnUI": false,
+        "assets": [{ "id": asset_id, "total": "1", "available": "1" }],
+        "autoFuel": false
+    })
+}
+
+// Helper to mock two pages of vault accounts, linked by the `after` cursor
+async fn mock_vault_pages(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/v1/vault/accounts_paged"))
+        .and(query_param("after", "cursor-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "accounts": [vault_json("2", "ETH_TEST5")],
+            "paging": {}
+        })))
+        .with_priority(1)
+        .mount(server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/vault/accounts_paged"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "accounts": [vault_json("0", "ETH_TEST5"), vault_json("1", "BTC_TEST")],
+            "paging": { "after": "cursor-1" },
+            "nextUrl": "https://api.fireblocks.io/v1/vault/accounts_paged?after=cursor-1"
+        })))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_vault_accounts_stream_follows_pages() {
+    let server = MockServer::start().await;
+    let client = mock_client(&server).await;
+    mock_vault_pages(&server).await;
+
+    let vaults: Vec<_> = client
+        .vault_accounts_stream(VaultAccountsFilter::default())
+        .try_collect()
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = vaults.iter().map(|vault| vault.id.as_str()).collect();
+    assert_eq!(ids, ["0", "1", "2"]);
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_vault_accounts_stream_filters() {
+    let server = MockServer::start().await;
+    let client = mock_client(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/vault/accounts_paged"))
+        .and(query_param("namePrefix", "Treasury"))
+        .and(query_param("nameSuffix", "Hot"))
+        .and(query_param("minAmountThreshold", "0.5"))
+        .and(query_param("assetId", "ETH_TEST5"))
+        .and(query_param("orderBy", "DESC"))
+        .and(query_param("limit", "50"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "accounts": [vault_json("7", "ETH_TEST5")],
+            "paging": {}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let filter = VaultAccountsFilter::default()
+        .with_name_prefix("Treasury".to_string())
+        .with_name_suffix("Hot".to_string())
+        .with_min_amount_threshold(0.5)
+        .with_asset_id("ETH_TEST5".to_string())
+        .with_order_by(SortDirection::DESC)
+        .with_limit(50);
+    let vaults: Vec<_> = client
+        .vault_accounts_stream(filter)
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(vaults.len(), 1);
+    assert_eq!(vaults[0].id, "7");
+}
+
+#[tokio::test]
+async fn test_vault_accounts_stream_error() {
+    let server = MockServer::start().await;
+    let client = mock_client(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/vault/accounts_paged"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let result: Result<Vec<_>, _> = client
+        .vault_accounts_stream(VaultAccountsFilter::default())
+        .try_collect()
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_populate_accounts_from_all_pages() {
+    let server = MockServer::start().await;
+    mock_vault_pages(&server).await;
+    mock_empty_address_book(&server).await;
+
+    for (vault_id, address) in [("0", VAULT_ADDRESS), ("2", SECOND_VAULT_ADDRESS)] {
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/v1/vault/accounts/{}/ETH_TEST5/addresses",
+                vault_id
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+                "assetId": "ETH_TEST5",
+                "address": address.to_string(),
+                "type": "Permanent"
+            }])))
+            .mount(&server)
+            .await;
+    }
+
+    let mut config = mock_config(&server).await;
+    config.vault_account_ids = None;
+    let provider = FireblocksProvider::new(config).await.unwrap();
+
+    assert_eq!(provider.get_vault_accounts().await.unwrap(), vec![0, 2]);
+    let accounts = provider.accounts.read().unwrap().clone();
+    assert_eq!(accounts.len(), 2);
+    assert_eq!(accounts[&0], VAULT_ADDRESS);
+    assert_eq!(accounts[&2], SECOND_VAULT_ADDRESS);
+}
+
+// An asset wallet as returned by the Fireblocks API
+fn asset_wallet_json(vault_id: &str, total: &str) -> serde_json::Value {
+    json!({
+        "vaultId": vault_id,
+        "assetId": "ETH_TEST5",
+        "available": total,
+        "total": total,
+        "pending": "0",
+        "staked": "0",
+        "frozen": "0",
+        "lockedAmount": "0",
+        "creationTimestamp": "1700000000000"
+    })
+}
+
+#[tokio::test]
+async fn test_get_asset_wallets() {
+    let server = MockServer::start().await;
+    let client = mock_client(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/vault/asset_wallets"))
+        .and(query_param("totalAmountLargerThan", "1.5"))
+        .and(query_param("assetId", "ETH_TEST5"))
+        .and(query_param("orderBy", "ASC"))
+        .and(query_param("limit", "10"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "assetWallets": [asset_wallet_json("0", "2")],
+            "paging": { "after": "cursor-1" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let filter = AssetWalletsFilter::default()
+        .with_total_amount_larger_than(1.5)
+        .with_asset_id("ETH_TEST5".to_string())
+        .with_order_by(SortDirection::ASC)
+        .with_limit(10);
+    let page = client.get_asset_wallets(&filter).await.unwrap();
+
+    assert_eq!(page.asset_wallets.len(), 1);
+    assert_eq!(page.asset_wallets[0].vault_id, "0");
+    assert_eq!(page.asset_wallets[0].total, "2");
+    assert_eq!(
+        page.asset_wallets[0].creation_time.as_deref(),
+        Some("1700000000000")
+    );
+    assert_eq!(page.paging.unwrap().after.as_deref(), Some("cursor-1"));
+}
+
+#[tokio::test]
+async fn test_asset_wallets_stream_follows_pages() {
+    let server = MockServer::start().await;
+    let client = mock_client(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/vault/asset_wallets"))
+        .and(query_param("after", "cursor-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "assetWallets": [asset_wallet_json("2", "3")],
+            "paging": {}
+        })))
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/vault/asset_wallets"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "assetWallets": [asset_wallet_json("0", "1"), asset_wallet_json("1", "2")],
+            "paging": { "after": "cursor-1" }
+        })))
+        .mount(&server)
+        .await;
+
+    let wallets: Vec<_> = client
+        .asset_wallets_stream(AssetWalletsFilter::default())
+        .try_collect()
+        .await
+        .unwrap();
+
+    let vault_ids: Vec<_> = wallets
+        .iter()
+        .map(|wallet| wallet.vault_id.as_str())
+        .collect();
+    assert_eq!(vault_ids, ["0", "1", "2"]);
+}
+
+#[tokio::test]
+async fn test_pager_collect_all() {
+    let server = MockServer::start().await;
+    let client = mock_client(&server).await;
+    mock_vault_pages(&server).await;
+
+    let vaults = client
+        .pager(VaultAccountsFilter::default())
+        .with_max_items(3)
+        .collect_all()
+        .await
+        .unwrap();
+    assert_eq!(vaults.len(), 3);
+}
+
+#[tokio::test]
+async fn test_pager_max_items() {
+    let server = MockServer::start().await;
+    let client = mock_client(&server).await;
+    mock_vault_pages(&server).await;
+
+    match client
+        .pager(VaultAccountsFilter::default())
+        .with_max_items(1)
+        .collect_all()
+        .await
+    {
+        Err(FireblocksError::PaginationLimitError(max_items)) => assert_eq!(max_items, 1),
+        other => panic!("Expected the pagination limit, got {:?}", other),
+    }
+
+    // The guard trips before fetching the next page
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_pager_fetch_page() {
+    let server = MockServer::start().await;
+    let client = mock_client(&server).await;
+    mock_vault_pages(&server).await;
+
+    let pager = client.pager(VaultAccountsFilter::default());
+    let (vaults, next) = pager
+        .fetch_page(&VaultAccountsFilter::default())
+        .await
+        .unwrap();
+    assert_eq!(vaults.len(), 2);
+    let next = next.unwrap();
+    assert_eq!(next.after.as_deref(), Some("cursor-1"));
+
+    let (vaults, next) = pager.fetch_page(&next).await.unwrap();
+    assert_eq!(vaults.len(), 1);
+    assert!(next.is_none());
+}
+
+#[tokio::test]
+async fn test_create_deposit_address() {
+    let server = MockServer::start().await;
+    let client = mock_client(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/vault/accounts/0/ETH_TEST5/addresses"))
+        .and(body_json(json!({ "description": "Payouts" })))
+        .and(header("Idempotency-Key", "address-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "address": VAULT_ADDRESS.to_string(),
+            "bip44AddressIndex": 1
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let address_args = CreateAddressRequest {
+        description: Some("Payouts".to_string()),
+        customer_ref_id: None,
+    };
+    let request_opts = RequestOptions::default().with_idempotency_key("address-1".to_string());
+    let address = client
+        .create_deposit_address("0", "ETH_TEST5", &address_args, &request_opts)
+        .await
+        .unwrap();
+
+    assert_eq!(address.address, VAULT_ADDRESS.to_string());
+    assert_eq!(address.bip44_address_index, Some(1));
+}
@@ -0,0 +1,200 @@
+mod common;
+
+use alloy_consensus::{SignableTransaction, TxEip1559, TypedTransaction};
+use alloy_core::primitives::{address, b256, TxKind, U256};
+use alloy_network::{Ethereum, NetworkWallet, TransactionBuilder};
+use alloy_provider::{Provider, ProviderBuilder};
+use alloy_rpc_types_eth::TransactionRequest;
+use serde_json::json;
+use wiremock::{
+    matchers::{body_partial_json, method, path},
+    Mock, MockServer, Request, ResponseTemplate,
+};
+
+use alloy_fireblocks::{
+    provider::FireblocksProvider, signer::FireblocksSigner, wallet::FireblocksWallet,
+};
+use common::*;
+
+// Helper to create a provider caching two vault accounts
+async fn mock_multi_vault_provider(server: &MockServer) -> FireblocksProvider {
+    for (vault_id, address) in [(0, VAULT_ADDRESS), (1, SECOND_VAULT_ADDRESS)] {
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/v1/vault/accounts/{}/ETH_TEST5/addresses",
+                vault_id
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+                "assetId": "ETH_TEST5",
+                "address": address.to_string(),
+                "type": "Permanent"
+            }])))
+            .mount(server)
+            .await;
+    }
+
+    let config = mock_config(server).await.with_vault_account_ids(vec![0, 1]);
+    FireblocksProvider::new(config).await.unwrap()
+}
+
+// Helper to build a fully populated EIP-1559 transaction
+fn eip1559_tx() -> TxEip1559 {
+    TxEip1559 {
+        chain_id: 11155111,
+        nonce: 3,
+        gas_limit: 21_000,
+        max_fee_per_gas: 20_000_000_000,
+        max_priority_fee_per_gas: 1_000_000_000,
+        to: TxKind::Call(address!("000000000000000000000000000000000000dEaD")),
+        value: U256::from(1_000_000_000_000_000u64),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_provider_wallet() {
+    let server = MockServer::start().await;
+    let provider = mock_multi_vault_provider(&server).await;
+
+    let wallet = provider.wallet().unwrap();
+
+    assert_eq!(
+        NetworkWallet::<Ethereum>::default_signer_address(&wallet),
+        VAULT_ADDRESS
+    );
+    assert_eq!(
+        wallet.accounts().into_iter().collect::<Vec<_>>(),
+        vec![(0, VAULT_ADDRESS), (1, SECOND_VAULT_ADDRESS)]
+    );
+    assert!(NetworkWallet::<Ethereum>::has_signer_for(
+        &wallet,
+        &SECOND_VAULT_ADDRESS
+    ));
+    assert_eq!(wallet.signer_by_vault_id(1).unwrap().vault_id(), 1);
+}
+
+#[tokio::test]
+async fn test_wallet_default_signer() {
+    let server = MockServer::start().await;
+    let provider = mock_multi_vault_provider(&server).await;
+
+    let wallet = provider
+        .wallet()
+        .unwrap()
+        .with_default_signer(SECOND_VAULT_ADDRESS)
+        .unwrap();
+    assert_eq!(
+        NetworkWallet::<Ethereum>::default_signer_address(&wallet),
+        SECOND_VAULT_ADDRESS
+    );
+
+    let unknown = address!("000000000000000000000000000000000000dEaD");
+    assert!(wallet.with_default_signer(unknown).is_err());
+}
+
+#[tokio::test]
+async fn test_wallet_signs_with_sender_vault() {
+    let server = MockServer::start().await;
+    let provider = mock_multi_vault_provider(&server).await;
+    let wallet = provider.wallet().unwrap();
+
+    let tx = eip1559_tx();
+    mock_signing_tx(
+        &server,
+        "tx-wallet-sign",
+        signature_with_key(SECOND_VAULT_KEY, tx.signature_hash()),
+    )
+    .await;
+
+    let envelope = NetworkWallet::<Ethereum>::sign_transaction_from(
+        &wallet,
+        SECOND_VAULT_ADDRESS,
+        TypedTransaction::Eip1559(tx),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        envelope
+            .signature()
+            .recover_address_from_prehash(&envelope.signature_hash())
+            .unwrap(),
+        SECOND_VAULT_ADDRESS
+    );
+
+    // The signing request is sourced from the sender's vault
+    let requests = server.received_requests().await.unwrap();
+    let body: serde_json::Value = requests
+        .iter()
+        .find(|req| req.method.as_str() == "POST")
+        .map(|req| serde_json::from_slice(&req.body).unwrap())
+        .unwrap();
+    assert_eq!(body["source"]["id"], json!("1"));
+}
+
+#[tokio::test]
+async fn test_wallet_unknown_sender() {
+    let server = MockServer::start().await;
+    let wallet = FireblocksWallet::from(FireblocksSigner::new(
+        mock_client(&server).await,
+        0,
+        "ETH_TEST5".to_string(),
+        VAULT_ADDRESS,
+    ));
+
+    let result = NetworkWallet::<Ethereum>::sign_transaction_from(
+        &wallet,
+        SECOND_VAULT_ADDRESS,
+        TypedTransaction::Eip1559(eip1559_tx()),
+    )
+    .await;
+    assert!(result.is_err());
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_wallet_with_provider_builder() {
+    let server = MockServer::start().await;
+    let provider = mock_multi_vault_provider(&server).await;
+
+    let tx = eip1559_tx();
+    mock_signing_tx(
+        &server,
+        "tx-wallet-send",
+        signature_with_key(SECOND_VAULT_KEY, tx.signature_hash()),
+    )
+    .await;
+
+    let tx_hash = b256!("0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060");
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(body_partial_json(
+            json!({ "method": "eth_sendRawTransaction" }),
+        ))
+        .respond_with(move |req: &Request| {
+            let body: serde_json::Value = serde_json::from_slice(&req.body).unwrap();
+            ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": body["id"],
+                "result": tx_hash
+            }))
+        })
+        .mount(&server)
+        .await;
+
+    let client = ProviderBuilder::new()
+        .wallet(provider.wallet().unwrap())
+        .on_http(server.uri().parse().unwrap());
+
+    let request = TransactionRequest::default()
+        .with_from(SECOND_VAULT_ADDRESS)
+        .with_to(address!("000000000000000000000000000000000000dEaD"))
+        .with_value(tx.value)
+        .with_nonce(tx.nonce)
+        .with_chain_id(tx.chain_id)
+        .with_gas_limit(tx.gas_limit)
+        .with_max_fee_per_gas(tx.max_fee_per_gas)
+        .with_max_priority_fee_per_gas(tx.max_priority_fee_per_gas);
+
+    let pending = client.send_transaction(request).await.unwrap();
+    assert_eq!(*pending.tx_hash(), tx_hash);
+}